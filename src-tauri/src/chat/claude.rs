@@ -303,7 +303,8 @@ fn build_claude_args(
 
     // Model (strip "-fast" suffix: "opus-fast" → model="opus" + fastMode setting)
     let is_fast = if let Some(m) = model {
-        let (actual_model, fast) = split_fast_model(m);
+        let m = super::models::normalize_model(&super::types::Backend::Claude, m);
+        let (actual_model, fast) = split_fast_model(&m);
         args.push("--model".to_string());
         args.push(actual_model.to_string());
         fast
@@ -822,6 +823,21 @@ pub fn execute_claude_detached(
         return Err(error_msg);
     }
 
+    // Held until this function returns, covering the spawn and the tail
+    let _tailer_slot = match super::ensure_tailer_capacity(app) {
+        Ok(slot) => slot,
+        Err(error_msg) => {
+            log::warn!("{error_msg}");
+            let error_event = ErrorEvent {
+                session_id: session_id.to_string(),
+                worktree_id: worktree_id.to_string(),
+                error: error_msg.clone(),
+            };
+            let _ = app.emit_all("chat:error", &error_event);
+            return Err(error_msg);
+        }
+    };
+
     // Build args
     let (args, env_vars) = build_claude_args(
         app,
@@ -906,19 +922,18 @@ pub fn execute_claude_detached(
 
     // Tail the output file for real-time updates
     // Use match to ensure unregister_process is always called, even on error
-    super::increment_tailer_count();
-    let response = match tail_claude_output(app, session_id, worktree_id, output_file, pid) {
-        Ok(resp) => {
-            super::decrement_tailer_count();
-            super::registry::unregister_process(session_id);
-            resp
-        }
-        Err(e) => {
-            super::decrement_tailer_count();
-            super::registry::unregister_process(session_id);
-            return Err(e);
-        }
-    };
+    let tail_config = super::tail::TailConfig::for_backend(&super::types::Backend::Claude);
+    let response =
+        match tail_claude_output(app, session_id, worktree_id, output_file, pid, tail_config) {
+            Ok(resp) => {
+                super::registry::unregister_process(session_id);
+                resp
+            }
+            Err(e) => {
+                super::registry::unregister_process(session_id);
+                return Err(e);
+            }
+        };
 
     Ok((pid, response))
 }
@@ -942,10 +957,11 @@ pub fn tail_claude_output(
     worktree_id: &str,
     output_file: &std::path::Path,
     pid: u32,
+    tail_config: super::tail::TailConfig,
 ) -> Result<ClaudeResponse, String> {
     use super::detached::is_process_alive;
-    use super::tail::{NdjsonTailer, POLL_INTERVAL, POLL_INTERVAL_FAST};
-    use std::time::{Duration, Instant};
+    use super::tail::NdjsonTailer;
+    use std::time::Instant;
 
     log::trace!("Starting to tail NDJSON output for session: {session_id}");
     log::trace!("Output file: {output_file:?}, PID: {pid}");
@@ -963,12 +979,12 @@ pub fn tail_claude_output(
     let mut usage: Option<UsageData> = None;
     let mut error_lines: Vec<String> = Vec::new();
 
-    // Timeout configuration:
-    // - Startup timeout: Wait up to 120 seconds for first Claude output (API connection time)
-    // - Dead process timeout: After receiving output, wait 2 seconds for more if process seems dead
-    //   (Reduced from 10s since registry check now provides faster cancellation detection)
-    let startup_timeout = Duration::from_secs(120);
-    let dead_process_timeout = Duration::from_secs(2);
+    // Timeout configuration (see TailConfig):
+    // - Startup timeout: Wait for first Claude output (API connection time)
+    // - Dead process timeout: After receiving output, wait briefly for more if process seems dead
+    //   (Kept short since registry check now provides faster cancellation detection)
+    let startup_timeout = tail_config.startup_timeout;
+    let dead_process_timeout = tail_config.dead_process_timeout;
     let started_at = Instant::now();
     let mut last_output_time = Instant::now();
     let mut received_claude_output = false; // Track if we've received any Claude output (not our metadata)
@@ -1084,6 +1100,7 @@ pub fn tail_claude_output(
                                             input: input.clone(),
                                             output: None,
                                             parent_tool_use_id: current_parent_tool_use_id.clone(),
+                                            full_output_path: None,
                                         });
 
                                         content_blocks.push(ContentBlock::ToolUse {
@@ -1403,8 +1420,8 @@ pub fn tail_claude_output(
             // During startup, wait longer but check for complete failure
             let elapsed = started_at.elapsed();
 
-            // Early exit if process died during startup (5s grace for slow spawning)
-            if !process_alive && elapsed > Duration::from_secs(5) {
+            // Early exit if process died during startup (grace for slow spawning)
+            if !process_alive && elapsed > tail_config.startup_grace {
                 log::warn!(
                     "Process {pid} died during startup after {:.1}s with no Claude output",
                     elapsed.as_secs_f64()
@@ -1432,12 +1449,12 @@ pub fn tail_claude_output(
             }
         }
 
-        // Adaptive sleep: poll faster when actively receiving data (5ms)
-        // to reduce per-event latency, back off to 50ms when idle.
+        // Adaptive sleep: poll faster when actively receiving data
+        // to reduce per-event latency, back off when idle.
         std::thread::sleep(if had_data {
-            POLL_INTERVAL_FAST
+            tail_config.poll_interval_fast
         } else {
-            POLL_INTERVAL
+            tail_config.poll_interval
         });
     }
 
@@ -8,10 +8,13 @@
 //! directly since they don't need streaming.
 
 use super::claude::CancelledEvent;
-use super::types::{ContentBlock, PermissionDenial, PermissionDeniedEvent, ToolCall, UsageData};
+use super::models::normalize_model;
+use super::types::{
+    Backend, ContentBlock, PermissionDenial, PermissionDeniedEvent, TodoItem, ToolCall, UsageData,
+};
 use crate::http_server::EmitExt;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 // =============================================================================
 // Response type (same shape as ClaudeResponse)
@@ -33,6 +36,16 @@ pub struct CodexResponse {
     pub error_emitted: bool,
     /// Token usage for this response
     pub usage: Option<UsageData>,
+    /// Why the turn ended ("stop", "cancelled", "error", or a Codex-provided reason)
+    pub finish_reason: Option<String>,
+    /// Wall-clock duration of the turn in milliseconds
+    pub duration_ms: Option<u64>,
+    /// Whether Codex persisted the thread, i.e. the next turn can resume this conversation
+    pub resumable: bool,
+    /// Paths successfully edited by `file_change` items, de-duplicated
+    pub changed_files: Vec<String>,
+    /// Turn completed (not cancelled or failed) with no text and no tool calls
+    pub empty_response: bool,
 }
 
 // =============================================================================
@@ -63,6 +76,39 @@ struct ToolResultEvent {
     worktree_id: String,
     tool_use_id: String,
     output: String,
+    /// Set when `output` was truncated; fetch the rest with `read_tool_output`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    full_output_path: Option<String>,
+}
+
+/// Payload for `chat:tool_error` (a tool completed with a failure status)
+#[derive(serde::Serialize, Clone)]
+struct ToolErrorEvent {
+    session_id: String,
+    worktree_id: String,
+    tool_use_id: String,
+    message: String,
+}
+
+/// Payload for `chat:todo` (Codex's todo list; each event replaces the list with the same id)
+#[derive(serde::Serialize, Clone)]
+struct TodoEvent {
+    session_id: String,
+    worktree_id: String,
+    id: String,
+    items: Vec<TodoItem>,
+}
+
+/// `chat:tool_result` payload for a Codex `file_change`, splitting out which
+/// files were applied and which were rejected.
+#[derive(serde::Serialize, Clone)]
+struct FileChangeResultEvent {
+    session_id: String,
+    worktree_id: String,
+    tool_use_id: String,
+    output: String,
+    applied: Vec<String>,
+    rejected: Vec<String>,
 }
 
 #[derive(serde::Serialize, Clone)]
@@ -79,12 +125,85 @@ struct ThinkingEvent {
     content: String,
 }
 
+/// Payload for `chat:stderr` (debug-level Codex diagnostics, outside the JSONL stream)
+#[derive(serde::Serialize, Clone)]
+struct StderrEvent {
+    session_id: String,
+    worktree_id: String,
+    line: String,
+}
+
+/// Payload for `chat:empty_response` (turn completed but produced nothing to show)
+#[derive(serde::Serialize, Clone)]
+struct EmptyResponseEvent {
+    session_id: String,
+    worktree_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    finish_reason: Option<String>,
+}
+
+/// Payload for `chat:tool_progress` (live output from a running command)
+#[derive(serde::Serialize, Clone)]
+struct ToolProgressEvent {
+    session_id: String,
+    worktree_id: String,
+    tool_use_id: String,
+    /// Output appended since the previous progress event
+    output: String,
+}
+
+/// Payload for `chat:tool_denied` (a command blocked by the execution mode's sandbox)
+#[derive(serde::Serialize, Clone)]
+struct ToolDeniedEvent {
+    session_id: String,
+    worktree_id: String,
+    tool_use_id: String,
+    command: String,
+    message: String,
+}
+
+/// Payload for `chat:stalled` / `chat:resumed` (no Codex output for a while)
+#[derive(serde::Serialize, Clone)]
+struct StallEvent {
+    session_id: String,
+    worktree_id: String,
+    /// Seconds since the last event from Codex
+    idle_secs: u64,
+}
+
+/// Payload for `chat:context_warning` (conversation nearing the model's context window)
+#[derive(serde::Serialize, Clone)]
+struct ContextWarningEvent {
+    session_id: String,
+    worktree_id: String,
+    percent_used: u8,
+    context_window: u64,
+}
+
+/// Codex accepted the turn and is working, before any text or tool output
+#[derive(serde::Serialize, Clone)]
+struct TurnStartEvent {
+    session_id: String,
+    worktree_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    turn_id: Option<String>,
+}
+
 #[derive(serde::Serialize, Clone)]
 struct DoneEvent {
     session_id: String,
     worktree_id: String,
     /// True when a plan-mode run completed with content (Codex/Opencode only)
     waiting_for_plan: bool,
+    /// Why the turn ended, e.g. "stop" or "max_output_tokens" (Codex only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    finish_reason: Option<String>,
+    /// Turn duration in milliseconds (Codex only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duration_ms: Option<u64>,
+    /// Files the turn edited (Codex only)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    changed_files: Vec<String>,
 }
 
 #[derive(serde::Serialize, Clone)]
@@ -107,16 +226,128 @@ fn split_fast_model(model: &str) -> (&str, bool) {
     }
 }
 
+/// Share of the context window at which `chat:context_warning` fires
+const CONTEXT_WARNING_PERCENT: u8 = 80;
+
+/// Input context window in tokens for known Codex models. Only consulted when
+/// the server's usage update doesn't carry `modelContextWindow`.
+fn codex_context_window(model: &str) -> Option<u64> {
+    let model = normalize_model(&Backend::Codex, model);
+    let (base, _) = split_fast_model(&model);
+    match base {
+        m if m.starts_with("gpt-5") => Some(272_000),
+        m if m.starts_with("gpt-4.1") => Some(1_047_576),
+        "o3" | "o4-mini" | "codex-mini-latest" => Some(200_000),
+        _ => None,
+    }
+}
+
+/// Percentage of the context window used by the latest request, from a
+/// `thread/tokenUsage/updated` payload. Returns `(percent, window)`.
+fn context_percent_used(
+    token_usage: &serde_json::Value,
+    fallback_window: Option<u64>,
+) -> Option<(u8, u64)> {
+    let window = token_usage
+        .get("modelContextWindow")
+        .and_then(|v| v.as_u64())
+        .or(fallback_window)
+        .filter(|w| *w > 0)?;
+    // The last request's input is the live context size; totals accumulate across turns
+    let used = token_usage
+        .get("last")
+        .unwrap_or(token_usage)
+        .get("inputTokens")
+        .and_then(|v| v.as_u64())?;
+    let percent = (used.saturating_mul(100) / window).min(100) as u8;
+    Some((percent, window))
+}
+
+/// Codex `web_search` config value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SearchMode {
+    /// Fetch live results
+    Live,
+    /// Serve results from OpenAI's index (Codex's default; cheaper)
+    #[default]
+    Cached,
+    Disabled,
+}
+
+impl SearchMode {
+    /// Parse a preference value; unknown or empty values fall back to `Cached`.
+    pub fn from_pref(value: &str) -> Self {
+        match value {
+            "live" => SearchMode::Live,
+            "disabled" => SearchMode::Disabled,
+            _ => SearchMode::Cached,
+        }
+    }
+
+    fn as_config_value(self) -> &'static str {
+        match self {
+            SearchMode::Live => "live",
+            SearchMode::Cached => "cached",
+            SearchMode::Disabled => "disabled",
+        }
+    }
+}
+
+/// Legacy on/off flag: `true` → live, `false` → disabled.
+impl From<bool> for SearchMode {
+    fn from(enabled: bool) -> Self {
+        if enabled {
+            SearchMode::Live
+        } else {
+            SearchMode::Disabled
+        }
+    }
+}
+
+/// Codex `model_reasoning_summary` config value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReasonSummary {
+    Auto,
+    Concise,
+    Detailed,
+    /// Ask Codex not to emit reasoning summaries at all
+    None,
+}
+
+impl ReasonSummary {
+    /// Parse a preference value; unknown values leave Codex's default in place.
+    pub fn from_pref(value: &str) -> Option<Self> {
+        match value {
+            "auto" => Some(ReasonSummary::Auto),
+            "concise" => Some(ReasonSummary::Concise),
+            "detailed" => Some(ReasonSummary::Detailed),
+            "none" => Some(ReasonSummary::None),
+            _ => None,
+        }
+    }
+
+    fn as_config_value(self) -> &'static str {
+        match self {
+            ReasonSummary::Auto => "auto",
+            ReasonSummary::Concise => "concise",
+            ReasonSummary::Detailed => "detailed",
+            ReasonSummary::None => "none",
+        }
+    }
+}
+
 /// Build JSON-RPC params for `thread/start`.
 #[allow(clippy::too_many_arguments)]
 pub fn build_thread_start_params(
     working_dir: &std::path::Path,
     model: Option<&str>,
     execution_mode: Option<&str>,
-    search_enabled: bool,
+    search_mode: impl Into<SearchMode>,
     instructions_file: Option<&std::path::Path>,
     multi_agent_enabled: bool,
     max_agent_threads: Option<u32>,
+    reasoning_summary: Option<ReasonSummary>,
+    model_provider: Option<&str>,
 ) -> serde_json::Value {
     let mut params = serde_json::json!({
         "cwd": working_dir.to_string_lossy(),
@@ -126,7 +357,8 @@ pub fn build_thread_start_params(
 
     // Model (gpt-5.4-fast → model=gpt-5.4 + serviceTier=fast)
     if let Some(m) = model {
-        let (actual_model, is_fast) = split_fast_model(m);
+        let m = normalize_model(&Backend::Codex, m);
+        let (actual_model, is_fast) = split_fast_model(&m);
         log::debug!(
             "Codex thread params: model={actual_model}, fast={is_fast}, mode={:?}",
             execution_mode
@@ -159,7 +391,7 @@ pub fn build_thread_start_params(
     // Web search
     config.insert(
         "web_search".to_string(),
-        serde_json::json!(if search_enabled { "live" } else { "disabled" }),
+        serde_json::json!(search_mode.into().as_config_value()),
     );
 
     // Custom instructions file
@@ -182,6 +414,19 @@ pub fn build_thread_start_params(
         }
     }
 
+    // Custom provider (e.g. Azure OpenAI or a gateway defined in Codex config)
+    if let Some(provider) = model_provider {
+        config.insert("model_provider".to_string(), serde_json::json!(provider));
+    }
+
+    // Reasoning summary verbosity (unset = Codex default)
+    if let Some(summary) = reasoning_summary {
+        config.insert(
+            "model_reasoning_summary".to_string(),
+            serde_json::json!(summary.as_config_value()),
+        );
+    }
+
     if !config.is_empty() {
         params["config"] = serde_json::Value::Object(config);
     }
@@ -189,6 +434,28 @@ pub fn build_thread_start_params(
     params
 }
 
+/// Build JSON-RPC params for `thread/resume` from `thread/start` params,
+/// carrying over the fields a resume is allowed to override.
+pub fn build_thread_resume_params(
+    thread_id: &str,
+    start_params: &serde_json::Value,
+) -> serde_json::Value {
+    let mut params = serde_json::json!({ "threadId": thread_id, "persistExtendedHistory": true });
+    for key in &[
+        "model",
+        "cwd",
+        "approvalPolicy",
+        "sandbox",
+        "config",
+        "serviceTier",
+    ] {
+        if let Some(v) = start_params.get(key) {
+            params[key] = v.clone();
+        }
+    }
+    params
+}
+
 /// Build JSON-RPC params for `turn/start`.
 pub fn build_turn_start_params(
     thread_id: &str,
@@ -244,6 +511,129 @@ pub fn build_turn_start_params(
 // Execution via app-server
 // =============================================================================
 
+/// Make sure the turn's output file exists and is writable, retrying for up to
+/// `output_file_grace` on transient failures (e.g. the session directory still being created).
+fn ensure_output_file(
+    output_file: &std::path::Path,
+    tail_config: &super::tail::TailConfig,
+) -> Result<(), String> {
+    let started = std::time::Instant::now();
+    loop {
+        let result = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(output_file);
+        match result {
+            Ok(_) => return Ok(()),
+            Err(e) if started.elapsed() >= tail_config.output_file_grace => {
+                log::error!(
+                    "Codex output file {} unavailable: {e}",
+                    output_file.display()
+                );
+                return Err(format!(
+                    "Codex output file could not be created ({e}). Check disk space and permissions for {}.",
+                    output_file.display()
+                ));
+            }
+            Err(_) => std::thread::sleep(tail_config.poll_interval),
+        }
+    }
+}
+
+/// Placeholder thread ID used in dry runs that would start a new thread.
+const DRY_RUN_THREAD_ID: &str = "<new-thread-id>";
+
+/// Fully resolved Codex invocation for a chat turn, built without spawning anything.
+///
+/// `command`/`args`/`env` describe the `codex app-server` process and `requests`
+/// the JSON-RPC calls the turn would send, so a misbehaving run can be
+/// reproduced by hand.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CodexDryRun {
+    /// Shell-ready command line (binary + args)
+    pub command: String,
+    pub args: Vec<String>,
+    /// Codex-related environment variables the server would inherit
+    pub env: Vec<(String, String)>,
+    /// JSON-RPC requests in send order: thread start/resume, then `turn/start`
+    pub requests: Vec<serde_json::Value>,
+}
+
+/// Build a [`CodexDryRun`] from already-resolved thread and turn settings.
+#[allow(clippy::too_many_arguments)]
+pub fn build_codex_dry_run(
+    cli_path: &std::path::Path,
+    env: Vec<(String, String)>,
+    existing_thread_id: Option<&str>,
+    thread_params: serde_json::Value,
+    prompt: &str,
+    working_dir: &std::path::Path,
+    execution_mode: Option<&str>,
+    reasoning_effort: Option<&str>,
+    add_dirs: &[String],
+) -> CodexDryRun {
+    let args: Vec<String> = super::codex_server::APP_SERVER_ARGS
+        .iter()
+        .map(|a| a.to_string())
+        .collect();
+    let binary = cli_path.to_string_lossy();
+    let command = std::iter::once(binary.as_ref())
+        .chain(args.iter().map(String::as_str))
+        .map(shell_word)
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let thread_request = match existing_thread_id {
+        Some(tid) => serde_json::json!({
+            "method": "thread/resume",
+            "params": build_thread_resume_params(tid, &thread_params),
+        }),
+        None => serde_json::json!({ "method": "thread/start", "params": thread_params }),
+    };
+    let turn_params = build_turn_start_params(
+        existing_thread_id.unwrap_or(DRY_RUN_THREAD_ID),
+        prompt,
+        working_dir,
+        execution_mode,
+        reasoning_effort,
+        add_dirs,
+    );
+
+    CodexDryRun {
+        command,
+        args,
+        env,
+        requests: vec![
+            thread_request,
+            serde_json::json!({ "method": "turn/start", "params": turn_params }),
+        ],
+    }
+}
+
+/// Codex-related environment variables of this process, with anything that
+/// looks like a credential left out so the dry run is safe to share.
+pub fn codex_env_for_dry_run() -> Vec<(String, String)> {
+    let mut env: Vec<(String, String)> = std::env::vars()
+        .filter(|(k, _)| k.starts_with("CODEX_") || k.starts_with("OPENAI_"))
+        .filter(|(k, _)| !["KEY", "TOKEN", "SECRET"].iter().any(|s| k.contains(s)))
+        .collect();
+    env.sort();
+    env
+}
+
+/// Quote a word for a POSIX shell, leaving plain words untouched.
+fn shell_word(word: &str) -> String {
+    let plain = !word.is_empty()
+        && word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=@%+,".contains(c));
+    if plain {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', "'\\''"))
+    }
+}
+
 /// Execute a Codex chat message via the persistent app-server.
 ///
 /// Handles thread creation/resume, turn execution, event mapping, and approvals.
@@ -259,12 +649,14 @@ pub fn execute_codex_via_server(
     model: Option<&str>,
     execution_mode: Option<&str>,
     reasoning_effort: Option<&str>,
-    search_enabled: bool,
+    search_mode: SearchMode,
     add_dirs: &[String],
     prompt: &str,
     instructions_file: Option<&std::path::Path>,
     multi_agent_enabled: bool,
     max_agent_threads: Option<u32>,
+    reasoning_summary: Option<ReasonSummary>,
+    model_provider: Option<&str>,
 ) -> Result<CodexResponse, String> {
     use super::codex_server;
 
@@ -276,51 +668,63 @@ pub fn execute_codex_via_server(
         existing_thread_id.is_some()
     );
 
+    // Jean writes the turn's JSONL history itself; fail before starting the
+    // turn if it can't, rather than losing the history silently.
+    let tail_config = super::tail::TailConfig::for_backend(&Backend::Codex);
+    ensure_output_file(output_file, &tail_config)?;
+    // Held until this function returns, covering thread start through the last event
+    let _tailer_slot = match super::ensure_tailer_capacity(app) {
+        Ok(slot) => slot,
+        Err(error_msg) => {
+            log::warn!("{error_msg}");
+            let _ = app.emit_all(
+                "chat:error",
+                &ErrorEvent {
+                    session_id: session_id.to_string(),
+                    worktree_id: worktree_id.to_string(),
+                    error: error_msg.clone(),
+                },
+            );
+            return Err(error_msg);
+        }
+    };
+
     // Ensure the app-server is running
     codex_server::ensure_running(app)?;
 
-    // Start or resume thread
+    // Start or resume thread; the flag says whether Codex persisted it for resume.
     // Wrapped in a closure so we can decrement USAGE_COUNT on failure
     // (ensure_running incremented it, but no session is registered yet)
-    let thread_id = match (|| -> Result<String, String> {
+    let (thread_id, thread_resumable) = match (|| -> Result<(String, bool), String> {
         if let Some(tid) = existing_thread_id {
             // Resume existing thread
             let resume_params = build_thread_start_params(
                 working_dir,
                 model,
                 execution_mode,
-                search_enabled,
+                search_mode,
                 instructions_file,
                 multi_agent_enabled,
                 max_agent_threads,
+                reasoning_summary,
+                model_provider,
             );
-            let mut full_params =
-                serde_json::json!({ "threadId": tid, "persistExtendedHistory": true });
-            // Copy overridable fields
-            for key in &[
-                "model",
-                "cwd",
-                "approvalPolicy",
-                "sandbox",
-                "config",
-                "serviceTier",
-            ] {
-                if let Some(v) = resume_params.get(key) {
-                    full_params[key] = v.clone();
-                }
-            }
+            let full_params = build_thread_resume_params(tid, &resume_params);
             match codex_server::send_request("thread/resume", full_params) {
-                Ok(_) => Ok(tid.to_string()),
+                // A thread that resumed has a rollout to resume from next time too
+                Ok(_) => Ok((tid.to_string(), true)),
                 Err(e) => {
                     log::warn!("Failed to resume thread {tid}: {e}, starting new thread");
                     start_new_thread(
                         working_dir,
                         model,
                         execution_mode,
-                        search_enabled,
+                        search_mode,
                         instructions_file,
                         multi_agent_enabled,
                         max_agent_threads,
+                        reasoning_summary,
+                        model_provider,
                     )
                 }
             }
@@ -329,14 +733,16 @@ pub fn execute_codex_via_server(
                 working_dir,
                 model,
                 execution_mode,
-                search_enabled,
+                search_mode,
                 instructions_file,
                 multi_agent_enabled,
                 max_agent_threads,
+                reasoning_summary,
+                model_provider,
             )
         }
     })() {
-        Ok(tid) => tid,
+        Ok(thread) => thread,
         Err(e) => {
             // ensure_running incremented USAGE_COUNT but no session was registered
             codex_server::decrement_usage_count();
@@ -376,7 +782,6 @@ pub fn execute_codex_via_server(
     }
 
     // Process events until turn completes
-    super::increment_tailer_count();
     let response = process_turn_events(
         app,
         session_id,
@@ -385,9 +790,10 @@ pub fn execute_codex_via_server(
         output_file,
         is_plan_mode,
         is_build_mode,
+        model.and_then(codex_context_window),
+        &tail_config,
         &event_rx,
     );
-    super::decrement_tailer_count();
 
     // Cleanup
     codex_server::unregister_session(&thread_id);
@@ -398,30 +804,59 @@ pub fn execute_codex_via_server(
     if resp.thread_id.is_empty() {
         resp.thread_id = thread_id;
     }
+    resp.resumable = thread_resumable;
+
+    // Without a thread ID the session saves nothing to resume from, and the next
+    // message would silently start a fresh conversation.
+    if !resp.resumable && !resp.cancelled {
+        log::warn!("Codex turn for session {session_id} finished without a thread ID");
+        let _ = app.emit_all(
+            "chat:warning",
+            &super::opencode::WarningEvent {
+                session_id: session_id.to_string(),
+                worktree_id: worktree_id.to_string(),
+                message: "Codex conversation could not be saved for resume; the next message will start a new conversation.".to_string(),
+            },
+        );
+    }
+
+    log::debug!(
+        "Codex turn finished for session {session_id}: reason={:?}, duration_ms={:?}",
+        resp.finish_reason,
+        resp.duration_ms
+    );
+    if resp.empty_response {
+        log::warn!("Codex turn for session {session_id} returned an empty response");
+    }
 
     Ok(resp)
 }
 
-/// Start a new Codex thread via app-server.
+/// Start a new Codex thread via app-server. Returns the thread ID and whether
+/// the server persisted the thread so a later turn can resume it.
 fn start_new_thread(
     working_dir: &std::path::Path,
     model: Option<&str>,
     execution_mode: Option<&str>,
-    search_enabled: bool,
+    search_mode: SearchMode,
     instructions_file: Option<&std::path::Path>,
     multi_agent_enabled: bool,
     max_agent_threads: Option<u32>,
-) -> Result<String, String> {
+    reasoning_summary: Option<ReasonSummary>,
+    model_provider: Option<&str>,
+) -> Result<(String, bool), String> {
     use super::codex_server;
 
     let params = build_thread_start_params(
         working_dir,
         model,
         execution_mode,
-        search_enabled,
+        search_mode,
         instructions_file,
         multi_agent_enabled,
         max_agent_threads,
+        reasoning_summary,
+        model_provider,
     );
 
     let result = codex_server::send_request("thread/start", params)?;
@@ -432,8 +867,26 @@ fn start_new_thread(
         .ok_or("thread/start response missing thread.id")?
         .to_string();
 
-    log::info!("Started new Codex thread: {thread_id}");
-    Ok(thread_id)
+    let persisted = thread_is_persisted(&result);
+    log::info!("Started new Codex thread: {thread_id} (persisted={persisted})");
+    Ok((thread_id, persisted))
+}
+
+/// Whether a `thread/start` response describes a thread saved to disk. Ephemeral
+/// threads have no rollout `path`; servers that don't report either field always
+/// persist threads.
+fn thread_is_persisted(response: &serde_json::Value) -> bool {
+    let Some(thread) = response.get("thread") else {
+        return true;
+    };
+    if thread.get("ephemeral").and_then(|v| v.as_bool()) == Some(true) {
+        return false;
+    }
+    match thread.get("path") {
+        Some(serde_json::Value::String(path)) => !path.is_empty(),
+        Some(_) => false,
+        None => true,
+    }
 }
 
 /// Process turn events from the app-server, emitting Tauri events.
@@ -446,6 +899,8 @@ fn process_turn_events(
     output_file: &std::path::Path,
     is_plan_mode: bool,
     is_build_mode: bool,
+    context_window: Option<u64>,
+    tail_config: &super::tail::TailConfig,
     event_rx: &std::sync::mpsc::Receiver<super::codex_server::ServerEvent>,
 ) -> CodexResponse {
     use super::codex_server::ServerEvent;
@@ -457,12 +912,32 @@ fn process_turn_events(
     let mut tool_calls: Vec<ToolCall> = Vec::new();
     let mut content_blocks: Vec<ContentBlock> = Vec::new();
     let mut pending_tool_ids: HashMap<String, String> = HashMap::new();
+    let mut reasoning_streamed: HashMap<String, String> = HashMap::new();
+    let mut agent_messages = AgentMessages::default();
+    let tool_output_limit = ToolOutputLimit::new(tool_output_max_bytes(app), output_file);
+    let mut block_slots = BlockSlots::default();
+    let mut changed_files: Vec<String> = Vec::new();
+    let mut tool_progress = ToolProgress::default();
     let mut completed = false;
     let mut cancelled = false;
     let mut server_interrupted = false;
     let mut error_emitted = false;
     let mut usage: Option<UsageData> = None;
     let mut received_completed_agent_message = false;
+    let turn_started_at = std::time::Instant::now();
+    let mut finish_reason: Option<String> = None;
+    let mut duration_ms: Option<u64> = None;
+    let mut context_warned = false;
+
+    // Stall tracking: warn the UI when nothing arrives for a while (0 = disabled)
+    let turn_timeout = tail_config.idle_timeout;
+    let stall_after = crate::load_preferences_sync(app)
+        .map(|p| p.codex_stall_warning_secs)
+        .unwrap_or(30);
+    let stall_after = (stall_after > 0).then(|| Duration::from_secs(stall_after.into()));
+    let mut last_event_at = std::time::Instant::now();
+    let mut stall_checked = false;
+    let mut stall_emitted = false;
 
     // Open output file for history
     let mut output_writer = std::fs::OpenOptions::new()
@@ -472,8 +947,31 @@ fn process_turn_events(
         .ok();
 
     loop {
-        let event = match event_rx.recv_timeout(Duration::from_secs(300)) {
+        let idle = last_event_at.elapsed();
+        let wait = match stall_after {
+            Some(stall) if !stall_checked => stall.min(turn_timeout).saturating_sub(idle),
+            _ => turn_timeout.saturating_sub(idle),
+        };
+        let event = match event_rx.recv_timeout(wait) {
             Ok(e) => e,
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout)
+                if last_event_at.elapsed() < turn_timeout =>
+            {
+                // Stall threshold reached; only worth reporting if Codex is still alive
+                stall_checked = true;
+                if super::codex_server::is_server_alive() {
+                    stall_emitted = true;
+                    let _ = app.emit_all(
+                        "chat:stalled",
+                        &StallEvent {
+                            session_id: session_id.to_string(),
+                            worktree_id: worktree_id.to_string(),
+                            idle_secs: last_event_at.elapsed().as_secs(),
+                        },
+                    );
+                }
+                continue;
+            }
             Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
                 log::warn!("Turn event timeout for session {session_id}");
                 let _ = app.emit_all(
@@ -494,6 +992,20 @@ fn process_turn_events(
             }
         };
 
+        if stall_emitted {
+            let _ = app.emit_all(
+                "chat:resumed",
+                &StallEvent {
+                    session_id: session_id.to_string(),
+                    worktree_id: worktree_id.to_string(),
+                    idle_secs: last_event_at.elapsed().as_secs(),
+                },
+            );
+        }
+        last_event_at = std::time::Instant::now();
+        stall_checked = false;
+        stall_emitted = false;
+
         match event {
             ServerEvent::Notification { method, params } => {
                 // Write to output file for history replay
@@ -515,6 +1027,11 @@ fn process_turn_events(
                     &mut tool_calls,
                     &mut content_blocks,
                     &mut pending_tool_ids,
+                    &mut reasoning_streamed,
+                    &mut agent_messages,
+                    &mut block_slots,
+                    &mut tool_progress,
+                    &tool_output_limit,
                     &mut completed,
                     &mut cancelled,
                     &mut server_interrupted,
@@ -523,6 +1040,66 @@ fn process_turn_events(
                     &mut received_completed_agent_message,
                 );
 
+                if method == "item/completed" {
+                    let item = normalize_item_types(
+                        params.get("item").unwrap_or(&serde_json::Value::Null),
+                    );
+                    match item.get("type").and_then(|v| v.as_str()) {
+                        Some("file_change") => {
+                            FileChangeSummary::from_item(&item).record_applied(&mut changed_files);
+                        }
+                        // Yolo mode runs without a sandbox, so only plan/build can deny
+                        Some("command_execution")
+                            if (is_plan_mode || is_build_mode) && is_sandbox_denial(&item) =>
+                        {
+                            let message = if is_plan_mode {
+                                "Blocked by the read-only sandbox in plan mode. Switch to build or yolo mode to allow it."
+                            } else {
+                                "Blocked by the workspace sandbox in build mode. Switch to yolo mode to allow it."
+                            };
+                            let _ = app.emit_all(
+                                "chat:tool_denied",
+                                &ToolDeniedEvent {
+                                    session_id: session_id.to_string(),
+                                    worktree_id: worktree_id.to_string(),
+                                    tool_use_id: item
+                                        .get("id")
+                                        .and_then(|v| v.as_str())
+                                        .unwrap_or_default()
+                                        .to_string(),
+                                    command: item
+                                        .get("command")
+                                        .and_then(|v| v.as_str())
+                                        .unwrap_or_default()
+                                        .to_string(),
+                                    message: message.to_string(),
+                                },
+                            );
+                        }
+                        _ => {}
+                    }
+                }
+
+                if method == "thread/tokenUsage/updated" && !context_warned {
+                    let pressure = params
+                        .get("tokenUsage")
+                        .and_then(|u| context_percent_used(u, context_window));
+                    if let Some((percent_used, window)) = pressure {
+                        if percent_used >= CONTEXT_WARNING_PERCENT {
+                            context_warned = true;
+                            let _ = app.emit_all(
+                                "chat:context_warning",
+                                &ContextWarningEvent {
+                                    session_id: session_id.to_string(),
+                                    worktree_id: worktree_id.to_string(),
+                                    percent_used,
+                                    context_window: window,
+                                },
+                            );
+                        }
+                    }
+                }
+
                 // Update turn_id for cancellation
                 if method == "turn/started" {
                     if let Some(turn_id) = params
@@ -537,6 +1114,17 @@ fn process_turn_events(
                         );
                     }
                 }
+
+                // Capture how and when the turn ended for the done event
+                if method == "turn/completed" {
+                    if let Some(turn) = params.get("turn") {
+                        finish_reason = turn_finish_reason(turn);
+                        duration_ms = turn.get("durationMs").and_then(|v| v.as_u64());
+                    }
+                    if duration_ms.is_none() {
+                        duration_ms = Some(turn_started_at.elapsed().as_millis() as u64);
+                    }
+                }
             }
             ServerEvent::ServerRequest { id, method, params } => {
                 // Write to output file
@@ -563,16 +1151,33 @@ fn process_turn_events(
                     is_build_mode,
                 );
             }
+            ServerEvent::Stderr(line) => {
+                let _ = app.emit_all(
+                    "chat:stderr",
+                    &StderrEvent {
+                        session_id: session_id.to_string(),
+                        worktree_id: worktree_id.to_string(),
+                        line,
+                    },
+                );
+            }
             ServerEvent::ServerDied => {
                 log::error!("Codex app-server died during turn for session {session_id}");
                 if !error_emitted {
+                    // Prefer the server's own last words over a generic message
+                    let error = match super::codex_server::last_stderr_line() {
+                        Some(line) => {
+                            format!("Codex server exited: {line}. Try sending your message again.")
+                        }
+                        None => "Codex server connection lost. Try sending your message again."
+                            .to_string(),
+                    };
                     let _ = app.emit_all(
                         "chat:error",
                         &ErrorEvent {
                             session_id: session_id.to_string(),
                             worktree_id: worktree_id.to_string(),
-                            error: "Codex server connection lost. Try sending your message again."
-                                .to_string(),
+                            error,
                         },
                     );
                     error_emitted = true;
@@ -609,6 +1214,20 @@ fn process_turn_events(
         }
     }
 
+    // A clean completion with nothing to show would render as a blank bubble
+    let empty_response =
+        !cancelled && !error_emitted && full_content.trim().is_empty() && tool_calls.is_empty();
+    if empty_response {
+        let _ = app.emit_all(
+            "chat:empty_response",
+            &EmptyResponseEvent {
+                session_id: session_id.to_string(),
+                worktree_id: worktree_id.to_string(),
+                finish_reason: finish_reason.clone(),
+            },
+        );
+    }
+
     // Emit chat:done unless error was emitted
     if !cancelled && !error_emitted {
         // Write result marker for crash-recovery compatibility
@@ -623,6 +1242,9 @@ fn process_turn_events(
                 session_id: session_id.to_string(),
                 worktree_id: worktree_id.to_string(),
                 waiting_for_plan: is_plan_mode && !full_content.is_empty(),
+                finish_reason: finish_reason.clone(),
+                duration_ms,
+                changed_files: changed_files.clone(),
             },
         );
     } else if server_interrupted && !error_emitted {
@@ -647,7 +1269,11 @@ fn process_turn_events(
         );
     }
 
+    block_slots.finish(&mut content_blocks);
+
     CodexResponse {
+        // Set by the caller from the thread start/resume result
+        resumable: false,
         content: full_content,
         thread_id: response_thread_id,
         tool_calls,
@@ -655,7 +1281,31 @@ fn process_turn_events(
         cancelled,
         error_emitted,
         usage,
+        finish_reason,
+        duration_ms,
+        changed_files,
+        empty_response,
+    }
+}
+
+/// Derive why a turn ended from an app-server `turn` object.
+/// Prefers an explicit reason when Codex provides one, otherwise maps the turn status.
+fn turn_finish_reason(turn: &serde_json::Value) -> Option<String> {
+    if let Some(reason) = turn
+        .get("finishReason")
+        .or_else(|| turn.get("stopReason"))
+        .and_then(|v| v.as_str())
+    {
+        return Some(reason.to_string());
     }
+    let status = turn.get("status").and_then(|v| v.as_str())?;
+    let reason = match status {
+        "completed" => "stop",
+        "interrupted" => "cancelled",
+        "failed" => "error",
+        other => other,
+    };
+    Some(reason.to_string())
 }
 
 /// Convert a server notification to old-format JSONL line for history compatibility.
@@ -734,6 +1384,11 @@ fn process_server_notification(
     tool_calls: &mut Vec<ToolCall>,
     content_blocks: &mut Vec<ContentBlock>,
     pending_tool_ids: &mut HashMap<String, String>,
+    reasoning_streamed: &mut HashMap<String, String>,
+    agent_messages: &mut AgentMessages,
+    block_slots: &mut BlockSlots,
+    tool_progress: &mut ToolProgress,
+    tool_output_limit: &ToolOutputLimit,
     completed: &mut bool,
     cancelled: &mut bool,
     server_interrupted: &mut bool,
@@ -744,6 +1399,22 @@ fn process_server_notification(
     log::trace!("[codex-server] Notification: {method} for session {session_id}");
 
     match method {
+        "turn/started" => {
+            // Lets the UI show "generating" while Codex thinks before its first token
+            let turn_id = params
+                .get("turn")
+                .and_then(|t| t.get("id"))
+                .and_then(|v| v.as_str())
+                .map(str::to_string);
+            let _ = app.emit_all(
+                "chat:turn_start",
+                &TurnStartEvent {
+                    session_id: session_id.to_string(),
+                    worktree_id: worktree_id.to_string(),
+                    turn_id,
+                },
+            );
+        }
         "thread/started" => {
             if let Some(tid) = params
                 .get("thread")
@@ -758,6 +1429,8 @@ fn process_server_notification(
             // Streaming text delta — emit immediately
             if let Some(delta) = params.get("delta").and_then(|v| v.as_str()) {
                 if !delta.is_empty() {
+                    let item_id = params.get("itemId").and_then(|v| v.as_str()).unwrap_or("");
+                    agent_messages.push_delta(item_id, delta);
                     full_content.push_str(delta);
                     let _ = app.emit_all(
                         "chat:chunk",
@@ -788,6 +1461,10 @@ fn process_server_notification(
                 tool_calls,
                 content_blocks,
                 pending_tool_ids,
+                reasoning_streamed,
+                agent_messages,
+                block_slots,
+                tool_output_limit,
                 completed,
                 usage,
                 error_emitted,
@@ -795,6 +1472,9 @@ fn process_server_notification(
         }
         "item/completed" => {
             let item = params.get("item").unwrap_or(&serde_json::Value::Null);
+            if let Some(item_id) = item.get("id").and_then(|v| v.as_str()) {
+                tool_progress.finish(item_id);
+            }
             let event_item = normalize_item_types(item);
             if event_item.get("type").and_then(|v| v.as_str()) == Some("agent_message") {
                 *received_completed_agent_message = true;
@@ -812,14 +1492,61 @@ fn process_server_notification(
                 tool_calls,
                 content_blocks,
                 pending_tool_ids,
+                reasoning_streamed,
+                agent_messages,
+                block_slots,
+                tool_output_limit,
                 completed,
                 usage,
                 error_emitted,
             );
         }
-        "item/commandExecution/outputDelta" | "item/fileChange/outputDelta" => {
-            // Streaming tool output — we could stream this but for now
-            // we let item/completed handle it with the final aggregated output
+        "item/updated" => {
+            // Progressive item state (reasoning text so far, todo list changes)
+            let item = params.get("item").unwrap_or(&serde_json::Value::Null);
+            let event_item = normalize_item_types(item);
+            let event_type = "item.updated";
+            let event_msg = serde_json::json!({ "type": event_type, "item": event_item });
+            process_codex_event(
+                app,
+                session_id,
+                worktree_id,
+                &event_msg,
+                event_type,
+                full_content,
+                thread_id,
+                tool_calls,
+                content_blocks,
+                pending_tool_ids,
+                reasoning_streamed,
+                agent_messages,
+                block_slots,
+                tool_output_limit,
+                completed,
+                usage,
+                error_emitted,
+            );
+        }
+        "item/commandExecution/outputDelta" => {
+            // Live output for long commands; item/completed still sets the final result
+            let item_id = params.get("itemId").and_then(|v| v.as_str()).unwrap_or("");
+            let delta = params.get("delta").and_then(|v| v.as_str()).unwrap_or("");
+            if let Some(tool_id) = pending_tool_ids.get(item_id) {
+                if let Some(output) = tool_progress.push(item_id, delta) {
+                    let _ = app.emit_all(
+                        "chat:tool_progress",
+                        &ToolProgressEvent {
+                            session_id: session_id.to_string(),
+                            worktree_id: worktree_id.to_string(),
+                            tool_use_id: tool_id.clone(),
+                            output,
+                        },
+                    );
+                }
+            }
+        }
+        "item/fileChange/outputDelta" => {
+            // File changes are summarized on item/completed
         }
         "turn/completed" => {
             // Extract usage from the turn object
@@ -878,9 +1605,15 @@ fn process_server_notification(
             }
         }
         "item/reasoning/textDelta" | "item/reasoning/summaryTextDelta" => {
-            // Streaming reasoning/thinking text
+            // Streaming reasoning/thinking text. Track what was streamed per item
+            // so item completion only emits the part the UI hasn't seen yet.
             if let Some(delta) = params.get("delta").and_then(|v| v.as_str()) {
                 if !delta.is_empty() {
+                    let item_id = params.get("itemId").and_then(|v| v.as_str()).unwrap_or("");
+                    reasoning_streamed
+                        .entry(item_id.to_string())
+                        .or_default()
+                        .push_str(delta);
                     let _ = app.emit_all(
                         "chat:thinking",
                         &ThinkingEvent {
@@ -922,6 +1655,198 @@ fn process_server_notification(
     }
 }
 
+/// Codex's own wording when it reports that its sandbox rejected a command.
+/// Generic OS errors ("permission denied", "operation not permitted") are left
+/// out: ordinary failures such as chmod or npm EACCES print them too.
+const SANDBOX_DENIAL_SIGNATURES: &[&str] = &["sandbox denied exec error", "failed in sandbox"];
+
+/// Why a completed command or file change failed, or `None` if it succeeded.
+/// Output alone can't tell (commands print errors on success too), so this
+/// goes by the item's status and exit code.
+fn tool_failure_message(item: &serde_json::Value) -> Option<String> {
+    let exit_code = item.get("exit_code").and_then(|v| v.as_i64());
+    let failed = item.get("status").and_then(|v| v.as_str()) == Some("failed")
+        || exit_code.is_some_and(|code| code != 0);
+    if !failed {
+        return None;
+    }
+    let message = match (item.get("type").and_then(|v| v.as_str()), exit_code) {
+        (Some("command_execution"), Some(code)) => format!("Command exited with code {code}"),
+        (Some("command_execution"), None) => "Command failed".to_string(),
+        _ => "File change failed".to_string(),
+    };
+    Some(message)
+}
+
+/// Record a failed tool as a `ContentBlock::Error` and emit `chat:tool_error`.
+fn emit_tool_error(
+    app: &tauri::AppHandle,
+    session_id: &str,
+    worktree_id: &str,
+    tool_id: &str,
+    message: String,
+    content_blocks: &mut Vec<ContentBlock>,
+) {
+    content_blocks.push(ContentBlock::Error {
+        tool_call_id: tool_id.to_string(),
+        message: message.clone(),
+    });
+    let _ = app.emit_all(
+        "chat:tool_error",
+        &ToolErrorEvent {
+            session_id: session_id.to_string(),
+            worktree_id: worktree_id.to_string(),
+            tool_use_id: tool_id.to_string(),
+            message,
+        },
+    );
+}
+
+/// Record an informational item (web search, image, compaction) as a tool call
+/// when it starts, so it shows while streaming. Returns the recorded call.
+fn start_info_tool(
+    item_type: &str,
+    item: &serde_json::Value,
+    tool_calls: &mut Vec<ToolCall>,
+    content_blocks: &mut Vec<ContentBlock>,
+    pending_tool_ids: &mut HashMap<String, String>,
+) -> ToolCall {
+    let item_id = item.get("id").and_then(|v| v.as_str()).unwrap_or("");
+    let tool_id = if item_id.is_empty() {
+        uuid::Uuid::new_v4().to_string()
+    } else {
+        item_id.to_string()
+    };
+    let tool = ToolCall {
+        id: tool_id.clone(),
+        name: codex_item_to_tool_name(item_type, item),
+        input: item.clone(),
+        output: None,
+        parent_tool_use_id: None,
+        full_output_path: None,
+    };
+    tool_calls.push(tool.clone());
+    content_blocks.push(ContentBlock::ToolUse {
+        tool_call_id: tool_id.clone(),
+    });
+    if !item_id.is_empty() {
+        pending_tool_ids.insert(item_id.to_string(), tool_id);
+    }
+    tool
+}
+
+/// Fill in the output of an informational tool started by `start_info_tool`.
+/// Returns `(tool_id, output)`, or `None` if its start was never seen.
+fn complete_info_tool(
+    item_type: &str,
+    item: &serde_json::Value,
+    tool_calls: &mut [ToolCall],
+    pending_tool_ids: &mut HashMap<String, String>,
+) -> Option<(String, String)> {
+    let item_id = item.get("id").and_then(|v| v.as_str()).unwrap_or("");
+    let tool_id = pending_tool_ids.remove(item_id)?;
+    let output = if item_type == "context_compaction" {
+        item.get("summary")
+            .and_then(|v| v.as_str())
+            .unwrap_or("Context compacted")
+            .to_string()
+    } else {
+        item.get("output")
+            .or_else(|| item.get("result"))
+            .map(|v| {
+                if let Some(s) = v.as_str() {
+                    s.to_string()
+                } else {
+                    serde_json::to_string(v).unwrap_or_default()
+                }
+            })
+            .unwrap_or_else(|| "completed".to_string())
+    };
+    if let Some(tc) = tool_calls.iter_mut().find(|t| t.id == tool_id) {
+        tc.output = Some(output.clone());
+    }
+    Some((tool_id, output))
+}
+
+/// Structured entries of a Codex `todo_list` item.
+fn todo_items(item: &serde_json::Value) -> Vec<TodoItem> {
+    item.get("items")
+        .and_then(|v| v.as_array())
+        .map(|entries| {
+            entries
+                .iter()
+                .filter_map(|entry| {
+                    let text = entry.get("text").and_then(|v| v.as_str())?;
+                    let completed = entry
+                        .get("completed")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or(false);
+                    Some(TodoItem {
+                        text: text.to_string(),
+                        status: if completed { "completed" } else { "pending" }.to_string(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Replace the todo block for `id` in place, or append it the first time the list appears.
+fn upsert_todo_block(content_blocks: &mut Vec<ContentBlock>, id: &str, items: Vec<TodoItem>) {
+    let existing = content_blocks.iter_mut().find_map(|block| match block {
+        ContentBlock::Todo {
+            id: block_id,
+            items: block_items,
+        } if block_id == id => Some(block_items),
+        _ => None,
+    });
+    match existing {
+        Some(block_items) => *block_items = items,
+        None => content_blocks.push(ContentBlock::Todo {
+            id: id.to_string(),
+            items,
+        }),
+    }
+}
+
+/// Record the current state of a `todo_list` item and emit `chat:todo`.
+fn emit_todo_list(
+    app: &tauri::AppHandle,
+    session_id: &str,
+    worktree_id: &str,
+    item: &serde_json::Value,
+    content_blocks: &mut Vec<ContentBlock>,
+) {
+    let id = item.get("id").and_then(|v| v.as_str()).unwrap_or("");
+    let items = todo_items(item);
+    upsert_todo_block(content_blocks, id, items.clone());
+    let _ = app.emit_all(
+        "chat:todo",
+        &TodoEvent {
+            session_id: session_id.to_string(),
+            worktree_id: worktree_id.to_string(),
+            id: id.to_string(),
+            items,
+        },
+    );
+}
+
+/// Whether a completed command failed because the sandbox blocked it, rather
+/// than failing on its own.
+fn is_sandbox_denial(item: &serde_json::Value) -> bool {
+    if tool_failure_message(item).is_none() {
+        return false;
+    }
+    let output = item
+        .get("aggregated_output")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    SANDBOX_DENIAL_SIGNATURES
+        .iter()
+        .any(|signature| output.contains(signature))
+}
+
 /// Normalize app-server camelCase item types to snake_case for backward compatibility
 /// with the existing process_codex_event function.
 fn normalize_item_types(item: &serde_json::Value) -> serde_json::Value {
@@ -948,6 +1873,9 @@ fn normalize_item_types(item: &serde_json::Value) -> serde_json::Value {
         if let Some(output) = obj.remove("aggregatedOutput") {
             obj.insert("aggregated_output".to_string(), output);
         }
+        if let Some(exit_code) = obj.remove("exitCode") {
+            obj.insert("exit_code".to_string(), exit_code);
+        }
         if let Some(states) = obj.remove("agentsStates") {
             obj.insert("agents_states".to_string(), states);
         }
@@ -1060,6 +1988,411 @@ fn format_codex_user_error(error_msg: &str) -> String {
     }
 }
 
+/// Full text of a reasoning item. Exec-style items carry `text`; app-server items
+/// carry `summary`/`content` arrays of strings.
+fn reasoning_item_text(item: &serde_json::Value) -> Option<String> {
+    if let Some(text) = item.get("text").and_then(|v| v.as_str()) {
+        return Some(text.to_string());
+    }
+    let parts: Vec<&str> = ["summary", "content"]
+        .iter()
+        .filter_map(|key| item.get(*key).and_then(|v| v.as_array()))
+        .flatten()
+        .filter_map(|v| v.as_str())
+        .collect();
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join("\n\n"))
+    }
+}
+
+/// Part of `full_text` that hasn't been streamed yet. When the streamed text isn't
+/// a prefix (e.g. reasoning summary deltas vs. full content), the UI already
+/// shows the item, so nothing more is emitted.
+fn unseen_text<'a>(full_text: &'a str, streamed: &str) -> &'a str {
+    full_text.strip_prefix(streamed).unwrap_or("")
+}
+
+/// Command output cap from the `codex_tool_output_max_kb` preference, in bytes
+pub fn tool_output_max_bytes(app: &tauri::AppHandle) -> usize {
+    let max_kb = crate::load_preferences_sync(app)
+        .map(|p| p.codex_tool_output_max_kb)
+        .unwrap_or_else(|_| crate::default_codex_tool_output_max_kb());
+    max_kb as usize * 1024
+}
+
+/// Size cap for command outputs and where oversized outputs are saved in full.
+/// Live turns and history reloads use the same cap so both show the same text.
+pub struct ToolOutputLimit {
+    /// Maximum bytes kept in `ToolCall.output` (0 = unlimited)
+    max_bytes: usize,
+    /// Session directory holding the run's output file
+    spill_dir: std::path::PathBuf,
+    run_id: String,
+}
+
+impl ToolOutputLimit {
+    /// Limit for the run logging to `output_file` (its JSONL run log)
+    pub fn new(max_bytes: usize, output_file: &std::path::Path) -> Self {
+        Self {
+            max_bytes,
+            spill_dir: output_file
+                .parent()
+                .map(|p| p.to_path_buf())
+                .unwrap_or_default(),
+            run_id: output_file
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or_default()
+                .to_string(),
+        }
+    }
+
+    /// Truncate an oversized output, saving the full text next to the run log.
+    /// Returns the kept text and, when truncated, the path of the full output.
+    fn apply(&self, tool_id: &str, output: String) -> (String, Option<String>) {
+        let (kept, omitted) = truncate_tool_output(&output, self.max_bytes);
+        if omitted == 0 {
+            return (output, None);
+        }
+        let truncated = format!("{kept}\n...[truncated, {omitted} bytes omitted]");
+        let path = self.spill_dir.join(spill_file_name(&self.run_id, tool_id));
+        // History reloads find the file already written by the live turn
+        if !path.exists() {
+            if let Err(e) = std::fs::write(&path, &output) {
+                log::warn!("Failed to save full tool output to {}: {e}", path.display());
+                return (truncated, None);
+            }
+        }
+        (truncated, Some(path.to_string_lossy().to_string()))
+    }
+}
+
+/// File name for a tool's full output. Codex item ids come from the server, so
+/// anything but ASCII alphanumerics, `-` and `_` is replaced before use.
+fn spill_file_name(run_id: &str, tool_id: &str) -> String {
+    let safe_id: String = tool_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{run_id}.{safe_id}{TOOL_OUTPUT_SUFFIX}")
+}
+
+/// Suffix of saved full tool outputs; `read_tool_output` only serves these files
+pub const TOOL_OUTPUT_SUFFIX: &str = ".output.txt";
+
+/// Split `output` at `max_bytes` (backing off to a char boundary).
+/// Returns the kept prefix and the number of bytes omitted; 0 = unlimited.
+fn truncate_tool_output(output: &str, max_bytes: usize) -> (&str, usize) {
+    if max_bytes == 0 || output.len() <= max_bytes {
+        return (output, 0);
+    }
+    let mut end = max_bytes;
+    while !output.is_char_boundary(end) {
+        end -= 1;
+    }
+    (&output[..end], output.len() - end)
+}
+
+/// Agent message text streamed so far, per item, so a completion emits only
+/// what its deltas missed (e.g. across a reconnect) and a repeated completion
+/// of the same item emits nothing.
+#[derive(Default)]
+struct AgentMessages {
+    /// Item ID → text already emitted as chunks
+    streamed: HashMap<String, String>,
+    /// Items whose completion has been handled
+    completed: HashSet<String>,
+}
+
+impl AgentMessages {
+    fn push_delta(&mut self, item_id: &str, delta: &str) {
+        self.streamed
+            .entry(item_id.to_string())
+            .or_default()
+            .push_str(delta);
+    }
+
+    /// `None` when this item already completed; otherwise the part of `text`
+    /// not yet emitted as chunks (possibly empty). Items without an ID can't
+    /// be told apart, so they are never treated as repeats.
+    fn complete<'a>(&mut self, item_id: &str, text: &'a str) -> Option<&'a str> {
+        if !item_id.is_empty() && !self.completed.insert(item_id.to_string()) {
+            return None;
+        }
+        let streamed = self.streamed.remove(item_id).unwrap_or_default();
+        Some(unseen_text(text, &streamed))
+    }
+}
+
+/// Minimum gap between `chat:tool_progress` events for one command
+const TOOL_PROGRESS_INTERVAL: std::time::Duration = std::time::Duration::from_millis(250);
+
+/// Batches streamed command output so a chatty command produces a few
+/// progress events per second instead of one per line.
+#[derive(Default)]
+struct ToolProgress {
+    /// Item ID → (output not yet emitted, when the last event went out)
+    pending: HashMap<String, (String, Option<std::time::Instant>)>,
+}
+
+impl ToolProgress {
+    /// Buffer `delta` and return everything unsent once the interval has passed.
+    fn push(&mut self, item_id: &str, delta: &str) -> Option<String> {
+        let (buffered, last_sent) = self.pending.entry(item_id.to_string()).or_default();
+        buffered.push_str(delta);
+        if last_sent.is_some_and(|at| at.elapsed() < TOOL_PROGRESS_INTERVAL) {
+            return None;
+        }
+        *last_sent = Some(std::time::Instant::now());
+        Some(std::mem::take(buffered))
+    }
+
+    /// The final result replaces any buffered output.
+    fn finish(&mut self, item_id: &str) {
+        self.pending.remove(item_id);
+    }
+}
+
+/// Keeps `content_blocks` in the order Codex started items.
+///
+/// Text and reasoning only materialize at `item.completed`, which can land
+/// after later items (e.g. a tool) have started. Reserving a placeholder at
+/// `item.started` and filling it on completion keeps the transcript faithful.
+#[derive(Default)]
+struct BlockSlots {
+    /// Item ID → index of its placeholder in `content_blocks`
+    reserved: HashMap<String, usize>,
+}
+
+impl BlockSlots {
+    fn reserve(&mut self, blocks: &mut Vec<ContentBlock>, item_id: &str) {
+        if item_id.is_empty() || self.reserved.contains_key(item_id) {
+            return;
+        }
+        self.reserved.insert(item_id.to_string(), blocks.len());
+        blocks.push(ContentBlock::Text {
+            text: String::new(),
+        });
+    }
+
+    /// Put `block` in the item's reserved slot, or append if none was reserved.
+    fn fill(&mut self, blocks: &mut Vec<ContentBlock>, item_id: &str, block: ContentBlock) {
+        match self.reserved.remove(item_id) {
+            Some(index) => blocks[index] = block,
+            None => blocks.push(block),
+        }
+    }
+
+    /// Drop placeholders for items that never produced content.
+    fn finish(self, blocks: &mut Vec<ContentBlock>) {
+        let mut unfilled: Vec<usize> = self.reserved.into_values().collect();
+        unfilled.sort_unstable_by(|a, b| b.cmp(a));
+        for index in unfilled {
+            blocks.remove(index);
+        }
+    }
+}
+
+/// Entries of a `file_change` item's `changes`, which may be a single object.
+fn file_change_entries(item: &serde_json::Value) -> Vec<serde_json::Value> {
+    match item.get("changes") {
+        Some(serde_json::Value::Array(changes)) => changes.clone(),
+        Some(change @ serde_json::Value::Object(_)) => vec![change.clone()],
+        _ => Vec::new(),
+    }
+}
+
+/// What a Codex `file_change` did to one file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ChangeKind {
+    Created,
+    Modified,
+    Deleted,
+}
+
+/// Classify one `changes` entry. Exec output reports the kind as a bare string
+/// (`"add"`), the app-server as an object (`{"type": "add"}`).
+fn classify_file_change(change: &serde_json::Value) -> ChangeKind {
+    let kind = change.get("kind");
+    let kind = kind
+        .and_then(|k| k.as_str())
+        .or_else(|| kind.and_then(|k| k.get("type")).and_then(|t| t.as_str()));
+    match kind {
+        Some("add" | "create" | "created") => ChangeKind::Created,
+        Some("delete" | "deleted" | "remove") => ChangeKind::Deleted,
+        _ => ChangeKind::Modified,
+    }
+}
+
+/// Tool input for a `file_change`: one `{ path, kind, move_path?, diff? }`
+/// entry per file, whichever shape Codex sent.
+fn normalize_file_changes(item: &serde_json::Value) -> serde_json::Value {
+    let entries = file_change_entries(item)
+        .iter()
+        .map(|change| {
+            let str_field = |value: Option<&serde_json::Value>| {
+                value.and_then(|v| v.as_str()).map(str::to_string)
+            };
+            let kind = change.get("kind");
+            let move_path = str_field(kind.and_then(|k| k.get("move_path")))
+                .or_else(|| str_field(kind.and_then(|k| k.get("movePath"))))
+                .or_else(|| str_field(change.get("move_path")));
+            let mut entry = serde_json::json!({
+                "path": str_field(change.get("path")).unwrap_or_else(|| "unknown".to_string()),
+                "kind": classify_file_change(change),
+            });
+            if let Some(move_path) = move_path {
+                entry["move_path"] = serde_json::json!(move_path);
+            }
+            if let Some(diff) = str_field(change.get("diff")) {
+                entry["diff"] = serde_json::json!(diff);
+            }
+            entry
+        })
+        .collect();
+    serde_json::Value::Array(entries)
+}
+
+/// Per-file outcome of a completed Codex `file_change` item.
+#[derive(Debug, Default, PartialEq)]
+struct FileChangeSummary {
+    applied: Vec<String>,
+    rejected: Vec<String>,
+}
+
+impl FileChangeSummary {
+    /// Split the item's changes by apply status. A per-change `status` wins;
+    /// otherwise the item-level status (`failed`/`declined`) applies to all.
+    fn from_item(item: &serde_json::Value) -> Self {
+        let is_rejected = |status: Option<&str>| {
+            matches!(status, Some("failed" | "declined" | "rejected" | "error"))
+        };
+        let item_rejected = is_rejected(item.get("status").and_then(|v| v.as_str()));
+        let mut summary = Self::default();
+        for change in &file_change_entries(item) {
+            let path = change
+                .get("path")
+                .and_then(|v| v.as_str())
+                .unwrap_or("unknown")
+                .to_string();
+            let rejected = match change.get("status").and_then(|v| v.as_str()) {
+                Some(status) => is_rejected(Some(status)),
+                None => item_rejected,
+            };
+            if rejected {
+                summary.rejected.push(path);
+            } else {
+                summary.applied.push(path);
+            }
+        }
+        summary
+    }
+
+    /// Tool output text, one line per outcome.
+    fn output(&self) -> String {
+        let mut lines = Vec::new();
+        if !self.applied.is_empty() {
+            lines.push(format!("Applied: {}", self.applied.join(", ")));
+        }
+        if !self.rejected.is_empty() {
+            lines.push(format!("Rejected: {}", self.rejected.join(", ")));
+        }
+        lines.join("\n")
+    }
+
+    /// Add applied paths to a turn's changed-file list, skipping repeats.
+    fn record_applied(&self, changed_files: &mut Vec<String>) {
+        for path in &self.applied {
+            if !changed_files.contains(path) {
+                changed_files.push(path.clone());
+            }
+        }
+    }
+
+    /// Tool error naming the rejected files, if any. Shown on the tool call
+    /// rather than in the assistant's text.
+    fn rejection_message(&self) -> Option<String> {
+        if self.rejected.is_empty() {
+            return None;
+        }
+        let files: Vec<String> = self.rejected.iter().map(|p| format!("`{p}`")).collect();
+        Some(format!(
+            "Changes to {} could not be applied",
+            files.join(", ")
+        ))
+    }
+}
+
+/// Tool input recorded for a Codex item when it starts.
+fn codex_item_tool_input(item_type: &str, item: &serde_json::Value) -> serde_json::Value {
+    match item_type {
+        "command_execution" => {
+            let command = item.get("command").and_then(|v| v.as_str()).unwrap_or("");
+            serde_json::json!({ "command": command })
+        }
+        "file_change" => normalize_file_changes(item),
+        "mcp_tool_call" => item
+            .get("arguments")
+            .cloned()
+            .unwrap_or(serde_json::Value::Null),
+        _ => item.clone(),
+    }
+}
+
+/// Treat a repeated `item.started` (reconnect, retry) as an update to the
+/// existing tool call instead of a second one. Returns true if it was a repeat.
+fn update_repeated_tool_start(
+    tool_calls: &mut [ToolCall],
+    item_type: &str,
+    item_id: &str,
+    item: &serde_json::Value,
+) -> bool {
+    if item_id.is_empty() {
+        return false;
+    }
+    let Some(tc) = tool_calls.iter_mut().find(|t| t.id == item_id) else {
+        return false;
+    };
+    log::debug!("Codex re-sent item.started for {item_id}; updating existing tool call");
+    tc.input = codex_item_tool_input(item_type, item);
+    true
+}
+
+/// Tool name shown for a Codex item.
+///
+/// Shared by the live stream and the history parser so both render the same
+/// tool UI; refine the mapping here rather than at either call site.
+pub fn codex_item_to_tool_name(item_type: &str, item: &serde_json::Value) -> String {
+    let field = |key: &str| item.get(key).and_then(|v| v.as_str()).unwrap_or("unknown");
+    match item_type {
+        "command_execution" => "Bash".to_string(),
+        "file_change" => "FileChange".to_string(),
+        "mcp_tool_call" => format!("mcp:{}:{}", field("server"), field("tool")),
+        "collab_tool_call" => match field("tool") {
+            "spawn_agent" => "SpawnAgent",
+            "send_input" => "SendInput",
+            "wait" => "WaitForAgents",
+            "close_agent" => "CloseAgent",
+            other => other,
+        }
+        .to_string(),
+        "web_search" => "CodexWebSearch".to_string(),
+        "image_generation" => "CodexImageGeneration".to_string(),
+        "image_view" => "CodexImageView".to_string(),
+        "context_compaction" => "CodexContextCompaction".to_string(),
+        other => other.to_string(),
+    }
+}
+
 /// Process a single Codex JSONL event. Shared between attached and detached tailers.
 #[allow(clippy::too_many_arguments)]
 fn process_codex_event(
@@ -1073,6 +2406,10 @@ fn process_codex_event(
     tool_calls: &mut Vec<ToolCall>,
     content_blocks: &mut Vec<ContentBlock>,
     pending_tool_ids: &mut HashMap<String, String>,
+    reasoning_streamed: &mut HashMap<String, String>,
+    agent_messages: &mut AgentMessages,
+    block_slots: &mut BlockSlots,
+    tool_output_limit: &ToolOutputLimit,
     completed: &mut bool,
     usage: &mut Option<UsageData>,
     error_emitted: &mut bool,
@@ -1089,9 +2426,14 @@ fn process_codex_event(
             let item_type = item.get("type").and_then(|v| v.as_str()).unwrap_or("");
             let item_id = item.get("id").and_then(|v| v.as_str()).unwrap_or("");
 
+            if update_repeated_tool_start(tool_calls, item_type, item_id, item) {
+                return;
+            }
+
             match item_type {
                 "command_execution" => {
                     let command = item.get("command").and_then(|v| v.as_str()).unwrap_or("");
+                    let tool_name = codex_item_to_tool_name(item_type, item);
                     let tool_id = if item_id.is_empty() {
                         uuid::Uuid::new_v4().to_string()
                     } else {
@@ -1099,10 +2441,11 @@ fn process_codex_event(
                     };
                     tool_calls.push(ToolCall {
                         id: tool_id.clone(),
-                        name: "Bash".to_string(),
+                        name: tool_name.clone(),
                         input: serde_json::json!({ "command": command }),
                         output: None,
                         parent_tool_use_id: None,
+                        full_output_path: None,
                     });
                     content_blocks.push(ContentBlock::ToolUse {
                         tool_call_id: tool_id.clone(),
@@ -1116,7 +2459,7 @@ fn process_codex_event(
                             session_id: session_id.to_string(),
                             worktree_id: worktree_id.to_string(),
                             id: tool_id.clone(),
-                            name: "Bash".to_string(),
+                            name: tool_name,
                             input: serde_json::json!({ "command": command }),
                             parent_tool_use_id: None,
                         },
@@ -1131,21 +2474,20 @@ fn process_codex_event(
                     );
                 }
                 "file_change" => {
+                    let tool_name = codex_item_to_tool_name(item_type, item);
                     let tool_id = if item_id.is_empty() {
                         uuid::Uuid::new_v4().to_string()
                     } else {
                         item_id.to_string()
                     };
-                    let changes = item
-                        .get("changes")
-                        .cloned()
-                        .unwrap_or(serde_json::Value::Null);
+                    let changes = normalize_file_changes(item);
                     tool_calls.push(ToolCall {
                         id: tool_id.clone(),
-                        name: "FileChange".to_string(),
+                        name: tool_name.clone(),
                         input: changes.clone(),
                         output: None,
                         parent_tool_use_id: None,
+                        full_output_path: None,
                     });
                     content_blocks.push(ContentBlock::ToolUse {
                         tool_call_id: tool_id.clone(),
@@ -1159,7 +2501,7 @@ fn process_codex_event(
                             session_id: session_id.to_string(),
                             worktree_id: worktree_id.to_string(),
                             id: tool_id.clone(),
-                            name: "FileChange".to_string(),
+                            name: tool_name,
                             input: changes,
                             parent_tool_use_id: None,
                         },
@@ -1174,14 +2516,6 @@ fn process_codex_event(
                     );
                 }
                 "mcp_tool_call" => {
-                    let server = item
-                        .get("server")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("unknown");
-                    let tool = item
-                        .get("tool")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("unknown");
                     let arguments = item
                         .get("arguments")
                         .cloned()
@@ -1191,13 +2525,14 @@ fn process_codex_event(
                     } else {
                         item_id.to_string()
                     };
-                    let name = format!("mcp:{server}:{tool}");
+                    let name = codex_item_to_tool_name(item_type, item);
                     tool_calls.push(ToolCall {
                         id: tool_id.clone(),
                         name: name.clone(),
                         input: arguments.clone(),
                         output: None,
                         parent_tool_use_id: None,
+                        full_output_path: None,
                     });
                     content_blocks.push(ContentBlock::ToolUse {
                         tool_call_id: tool_id.clone(),
@@ -1226,17 +2561,7 @@ fn process_codex_event(
                     );
                 }
                 "collab_tool_call" => {
-                    let collab_tool = item
-                        .get("tool")
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("unknown");
-                    let tool_name = match collab_tool {
-                        "spawn_agent" => "SpawnAgent",
-                        "send_input" => "SendInput",
-                        "wait" => "WaitForAgents",
-                        "close_agent" => "CloseAgent",
-                        _ => collab_tool,
-                    };
+                    let tool_name = codex_item_to_tool_name(item_type, item);
                     let tool_id = if item_id.is_empty() {
                         uuid::Uuid::new_v4().to_string()
                     } else {
@@ -1245,10 +2570,11 @@ fn process_codex_event(
                     let input = item.clone();
                     tool_calls.push(ToolCall {
                         id: tool_id.clone(),
-                        name: tool_name.to_string(),
+                        name: tool_name.clone(),
                         input: input.clone(),
                         output: None,
                         parent_tool_use_id: None,
+                        full_output_path: None,
                     });
                     content_blocks.push(ContentBlock::ToolUse {
                         tool_call_id: tool_id.clone(),
@@ -1262,7 +2588,7 @@ fn process_codex_event(
                             session_id: session_id.to_string(),
                             worktree_id: worktree_id.to_string(),
                             id: tool_id.clone(),
-                            name: tool_name.to_string(),
+                            name: tool_name,
                             input,
                             parent_tool_use_id: None,
                         },
@@ -1277,83 +2603,27 @@ fn process_codex_event(
                     );
                 }
                 "todo_list" => {
-                    let tool_id = if item_id.is_empty() {
-                        uuid::Uuid::new_v4().to_string()
-                    } else {
-                        item_id.to_string()
-                    };
-                    let input = item.clone();
-                    tool_calls.push(ToolCall {
-                        id: tool_id.clone(),
-                        name: "CodexTodoList".to_string(),
-                        input: input.clone(),
-                        output: None,
-                        parent_tool_use_id: None,
-                    });
-                    content_blocks.push(ContentBlock::ToolUse {
-                        tool_call_id: tool_id.clone(),
-                    });
-                    if !item_id.is_empty() {
-                        pending_tool_ids.insert(item_id.to_string(), tool_id.clone());
-                    }
-                    let _ = app.emit_all(
-                        "chat:tool_use",
-                        &ToolUseEvent {
-                            session_id: session_id.to_string(),
-                            worktree_id: worktree_id.to_string(),
-                            id: tool_id.clone(),
-                            name: "CodexTodoList".to_string(),
-                            input,
-                            parent_tool_use_id: None,
-                        },
-                    );
-                    let _ = app.emit_all(
-                        "chat:tool_block",
-                        &ToolBlockEvent {
-                            session_id: session_id.to_string(),
-                            worktree_id: worktree_id.to_string(),
-                            tool_call_id: tool_id,
-                        },
-                    );
+                    emit_todo_list(app, session_id, worktree_id, item, content_blocks);
                 }
-                // These types are handled on completion only (via deltas / dedicated events)
-                "agent_message" | "reasoning" | "user_message" => {}
+                // Handled on completion only (via deltas / dedicated events)
+                "user_message" => {}
                 // Informational tool-like events — surface as tool calls in the UI
                 "web_search" | "image_generation" | "image_view" | "context_compaction" => {
-                    let tool_name = match item_type {
-                        "web_search" => "CodexWebSearch",
-                        "image_generation" => "CodexImageGeneration",
-                        "image_view" => "CodexImageView",
-                        "context_compaction" => "CodexContextCompaction",
-                        _ => unreachable!(),
-                    };
-                    let tool_id = if item_id.is_empty() {
-                        uuid::Uuid::new_v4().to_string()
-                    } else {
-                        item_id.to_string()
-                    };
-                    let input = item.clone();
-                    tool_calls.push(ToolCall {
-                        id: tool_id.clone(),
-                        name: tool_name.to_string(),
-                        input: input.clone(),
-                        output: None,
-                        parent_tool_use_id: None,
-                    });
-                    content_blocks.push(ContentBlock::ToolUse {
-                        tool_call_id: tool_id.clone(),
-                    });
-                    if !item_id.is_empty() {
-                        pending_tool_ids.insert(item_id.to_string(), tool_id.clone());
-                    }
+                    let tool = start_info_tool(
+                        item_type,
+                        item,
+                        tool_calls,
+                        content_blocks,
+                        pending_tool_ids,
+                    );
                     let _ = app.emit_all(
                         "chat:tool_use",
                         &ToolUseEvent {
                             session_id: session_id.to_string(),
                             worktree_id: worktree_id.to_string(),
-                            id: tool_id.clone(),
-                            name: tool_name.to_string(),
-                            input,
+                            id: tool.id.clone(),
+                            name: tool.name,
+                            input: tool.input,
                             parent_tool_use_id: None,
                         },
                     );
@@ -1362,10 +2632,14 @@ fn process_codex_event(
                         &ToolBlockEvent {
                             session_id: session_id.to_string(),
                             worktree_id: worktree_id.to_string(),
-                            tool_call_id: tool_id,
+                            tool_call_id: tool.id,
                         },
                     );
                 }
+                // Text/reasoning arrive on completion; hold their place now
+                "agent_message" | "reasoning" => {
+                    block_slots.reserve(content_blocks, item_id);
+                }
                 other => {
                     log::debug!("Unknown Codex item.started type: {other}");
                 }
@@ -1383,19 +2657,27 @@ fn process_codex_event(
                     // Only push the content block here for the final CodexResponse.
                     if let Some(text) = item.get("text").and_then(|v| v.as_str()) {
                         if !text.is_empty() {
-                            content_blocks.push(ContentBlock::Text {
-                                text: text.to_string(),
-                            });
-                            // If no deltas were received (edge case), full_content
-                            // would be missing this text — emit chunk as fallback.
-                            if !full_content.contains(text) {
-                                full_content.push_str(text);
+                            // A repeated completion of the same item adds nothing
+                            let Some(missing) = agent_messages.complete(item_id, text) else {
+                                return;
+                            };
+                            block_slots.fill(
+                                content_blocks,
+                                item_id,
+                                ContentBlock::Text {
+                                    text: text.to_string(),
+                                },
+                            );
+                            // Deltas may have been missed (or only partly received);
+                            // emit whatever wasn't streamed for this item.
+                            if !missing.is_empty() {
+                                full_content.push_str(missing);
                                 let _ = app.emit_all(
                                     "chat:chunk",
                                     &ChunkEvent {
                                         session_id: session_id.to_string(),
                                         worktree_id: worktree_id.to_string(),
-                                        content: text.to_string(),
+                                        content: missing.to_string(),
                                     },
                                 );
                             }
@@ -1410,53 +2692,88 @@ fn process_codex_event(
                         .to_string();
                     let tool_id = pending_tool_ids.remove(item_id).unwrap_or_default();
                     if !tool_id.is_empty() {
+                        let (output, full_output_path) = tool_output_limit.apply(&tool_id, output);
                         if let Some(tc) = tool_calls.iter_mut().find(|t| t.id == tool_id) {
                             tc.output = Some(output.clone());
+                            tc.full_output_path = full_output_path.clone();
                         }
                         let _ = app.emit_all(
                             "chat:tool_result",
                             &ToolResultEvent {
                                 session_id: session_id.to_string(),
                                 worktree_id: worktree_id.to_string(),
-                                tool_use_id: tool_id,
+                                tool_use_id: tool_id.clone(),
                                 output,
+                                full_output_path,
                             },
                         );
+                        if let Some(message) = tool_failure_message(item) {
+                            emit_tool_error(
+                                app,
+                                session_id,
+                                worktree_id,
+                                &tool_id,
+                                message,
+                                content_blocks,
+                            );
+                        }
                     }
                 }
                 "file_change" => {
-                    let changes = item
-                        .get("changes")
-                        .map(|v| serde_json::to_string(v).unwrap_or_default())
-                        .unwrap_or_default();
+                    let summary = FileChangeSummary::from_item(item);
+                    let output = summary.output();
                     let tool_id = pending_tool_ids.remove(item_id).unwrap_or_default();
                     if !tool_id.is_empty() {
                         if let Some(tc) = tool_calls.iter_mut().find(|t| t.id == tool_id) {
-                            tc.output = Some(changes.clone());
+                            tc.output = Some(output.clone());
                         }
                         let _ = app.emit_all(
                             "chat:tool_result",
-                            &ToolResultEvent {
+                            &FileChangeResultEvent {
                                 session_id: session_id.to_string(),
                                 worktree_id: worktree_id.to_string(),
-                                tool_use_id: tool_id,
-                                output: changes,
+                                tool_use_id: tool_id.clone(),
+                                output,
+                                applied: summary.applied.clone(),
+                                rejected: summary.rejected.clone(),
                             },
                         );
+                        // Name the rejected files when there are any; they say
+                        // more than the generic failure message.
+                        if let Some(message) = summary
+                            .rejection_message()
+                            .or_else(|| tool_failure_message(item))
+                        {
+                            emit_tool_error(
+                                app,
+                                session_id,
+                                worktree_id,
+                                &tool_id,
+                                message,
+                                content_blocks,
+                            );
+                        }
                     }
                 }
                 "reasoning" => {
-                    if let Some(text) = item.get("text").and_then(|v| v.as_str()) {
-                        content_blocks.push(ContentBlock::Thinking {
-                            thinking: text.to_string(),
-                        });
-                        let _ = app.emit_all(
-                            "chat:thinking",
-                            &ThinkingEvent {
-                                session_id: session_id.to_string(),
-                                worktree_id: worktree_id.to_string(),
-                                content: text.to_string(),
-                            },
+                    let streamed = reasoning_streamed.remove(item_id).unwrap_or_default();
+                    let text = reasoning_item_text(item).unwrap_or_else(|| streamed.clone());
+                    if !text.is_empty() {
+                        let unseen = unseen_text(&text, &streamed);
+                        if !unseen.is_empty() {
+                            let _ = app.emit_all(
+                                "chat:thinking",
+                                &ThinkingEvent {
+                                    session_id: session_id.to_string(),
+                                    worktree_id: worktree_id.to_string(),
+                                    content: unseen.to_string(),
+                                },
+                            );
+                        }
+                        block_slots.fill(
+                            content_blocks,
+                            item_id,
+                            ContentBlock::Thinking { thinking: text },
                         );
                     }
                 }
@@ -1483,6 +2800,7 @@ fn process_codex_event(
                                 worktree_id: worktree_id.to_string(),
                                 tool_use_id: tool_id,
                                 output,
+                                full_output_path: None,
                             },
                         );
                     }
@@ -1536,34 +2854,16 @@ fn process_codex_event(
                                 worktree_id: worktree_id.to_string(),
                                 tool_use_id: tool_id,
                                 output,
+                                full_output_path: None,
                             },
                         );
                     }
                 }
                 // Informational tool-like events — populate output for UI
                 "web_search" | "image_generation" | "image_view" | "context_compaction" => {
-                    let output = if item_type == "context_compaction" {
-                        item.get("summary")
-                            .and_then(|v| v.as_str())
-                            .unwrap_or("Context compacted")
-                            .to_string()
-                    } else {
-                        item.get("output")
-                            .or_else(|| item.get("result"))
-                            .map(|v| {
-                                if let Some(s) = v.as_str() {
-                                    s.to_string()
-                                } else {
-                                    serde_json::to_string(v).unwrap_or_default()
-                                }
-                            })
-                            .unwrap_or_else(|| "completed".to_string())
-                    };
-                    let tool_id = pending_tool_ids.remove(item_id).unwrap_or_default();
-                    if !tool_id.is_empty() {
-                        if let Some(tc) = tool_calls.iter_mut().find(|t| t.id == tool_id) {
-                            tc.output = Some(output.clone());
-                        }
+                    if let Some((tool_id, output)) =
+                        complete_info_tool(item_type, item, tool_calls, pending_tool_ids)
+                    {
                         let _ = app.emit_all(
                             "chat:tool_result",
                             &ToolResultEvent {
@@ -1571,10 +2871,15 @@ fn process_codex_event(
                                 worktree_id: worktree_id.to_string(),
                                 tool_use_id: tool_id,
                                 output,
+                                full_output_path: None,
                             },
                         );
                     }
                 }
+                // Final checklist state; same shape as item.updated
+                "todo_list" => {
+                    emit_todo_list(app, session_id, worktree_id, item, content_blocks);
+                }
                 // User's own input echoed back — no UI needed
                 "user_message" => {}
                 other => {
@@ -1582,30 +2887,30 @@ fn process_codex_event(
                 }
             }
         }
-        // item.updated — only emitted for todo_list per Codex source
+        // item.updated — todo_list progress, and progressive reasoning text
         "item.updated" => {
             let item = msg.get("item").unwrap_or(&serde_json::Value::Null);
             let item_type = item.get("type").and_then(|v| v.as_str()).unwrap_or("");
             let item_id = item.get("id").and_then(|v| v.as_str()).unwrap_or("");
 
-            if item_type == "todo_list" {
-                if let Some(tool_id) = pending_tool_ids.get(item_id) {
-                    let updated_input = item.clone();
-                    if let Some(tc) = tool_calls.iter_mut().find(|t| t.id == *tool_id) {
-                        tc.input = updated_input.clone();
+            if item_type == "reasoning" {
+                if let Some(text) = reasoning_item_text(item) {
+                    let streamed = reasoning_streamed.entry(item_id.to_string()).or_default();
+                    let unseen = unseen_text(&text, streamed).to_string();
+                    if !unseen.is_empty() {
+                        streamed.push_str(&unseen);
+                        let _ = app.emit_all(
+                            "chat:thinking",
+                            &ThinkingEvent {
+                                session_id: session_id.to_string(),
+                                worktree_id: worktree_id.to_string(),
+                                content: unseen,
+                            },
+                        );
                     }
-                    let _ = app.emit_all(
-                        "chat:tool_use",
-                        &ToolUseEvent {
-                            session_id: session_id.to_string(),
-                            worktree_id: worktree_id.to_string(),
-                            id: tool_id.clone(),
-                            name: "CodexTodoList".to_string(),
-                            input: updated_input,
-                            parent_tool_use_id: None,
-                        },
-                    );
                 }
+            } else if item_type == "todo_list" {
+                emit_todo_list(app, session_id, worktree_id, item, content_blocks);
             }
         }
         "turn.completed" => {
@@ -1679,6 +2984,7 @@ fn process_codex_event(
 pub fn parse_codex_run_to_message(
     lines: &[String],
     run: &super::types::RunEntry,
+    tool_output_limit: &ToolOutputLimit,
 ) -> Result<super::types::ChatMessage, String> {
     use super::types::{ChatMessage, MessageRole};
     use uuid::Uuid;
@@ -1688,6 +2994,8 @@ pub fn parse_codex_run_to_message(
     let mut content_blocks: Vec<ContentBlock> = Vec::new();
     let mut pending_tool_ids: std::collections::HashMap<String, String> =
         std::collections::HashMap::new();
+    let mut block_slots = BlockSlots::default();
+    let mut changed_files: Vec<String> = Vec::new();
 
     for line in lines {
         if line.trim().is_empty() {
@@ -1715,6 +3023,10 @@ pub fn parse_codex_run_to_message(
                 let item_type = item.get("type").and_then(|v| v.as_str()).unwrap_or("");
                 let item_id = item.get("id").and_then(|v| v.as_str()).unwrap_or("");
 
+                if update_repeated_tool_start(&mut tool_calls, item_type, item_id, item) {
+                    continue;
+                }
+
                 match item_type {
                     "command_execution" => {
                         let command = item.get("command").and_then(|v| v.as_str()).unwrap_or("");
@@ -1726,10 +3038,11 @@ pub fn parse_codex_run_to_message(
 
                         tool_calls.push(ToolCall {
                             id: tool_id.clone(),
-                            name: "Bash".to_string(),
+                            name: codex_item_to_tool_name(item_type, item),
                             input: serde_json::json!({ "command": command }),
                             output: None,
                             parent_tool_use_id: None,
+                            full_output_path: None,
                         });
                         content_blocks.push(ContentBlock::ToolUse {
                             tool_call_id: tool_id.clone(),
@@ -1739,10 +3052,7 @@ pub fn parse_codex_run_to_message(
                         }
                     }
                     "file_change" => {
-                        let changes = item
-                            .get("changes")
-                            .cloned()
-                            .unwrap_or(serde_json::Value::Null);
+                        let changes = normalize_file_changes(item);
                         let tool_id = if item_id.is_empty() {
                             Uuid::new_v4().to_string()
                         } else {
@@ -1751,10 +3061,11 @@ pub fn parse_codex_run_to_message(
 
                         tool_calls.push(ToolCall {
                             id: tool_id.clone(),
-                            name: "FileChange".to_string(),
+                            name: codex_item_to_tool_name(item_type, item),
                             input: changes,
                             output: None,
                             parent_tool_use_id: None,
+                            full_output_path: None,
                         });
                         content_blocks.push(ContentBlock::ToolUse {
                             tool_call_id: tool_id.clone(),
@@ -1764,14 +3075,6 @@ pub fn parse_codex_run_to_message(
                         }
                     }
                     "mcp_tool_call" => {
-                        let server = item
-                            .get("server")
-                            .and_then(|v| v.as_str())
-                            .unwrap_or("unknown");
-                        let tool = item
-                            .get("tool")
-                            .and_then(|v| v.as_str())
-                            .unwrap_or("unknown");
                         let arguments = item
                             .get("arguments")
                             .cloned()
@@ -1784,10 +3087,11 @@ pub fn parse_codex_run_to_message(
 
                         tool_calls.push(ToolCall {
                             id: tool_id.clone(),
-                            name: format!("mcp:{server}:{tool}"),
+                            name: codex_item_to_tool_name(item_type, item),
                             input: arguments,
                             output: None,
                             parent_tool_use_id: None,
+                            full_output_path: None,
                         });
                         content_blocks.push(ContentBlock::ToolUse {
                             tool_call_id: tool_id.clone(),
@@ -1798,17 +3102,6 @@ pub fn parse_codex_run_to_message(
                     }
                     // Multi-agent collab tools (history)
                     "collab_tool_call" => {
-                        let collab_tool = item
-                            .get("tool")
-                            .and_then(|v| v.as_str())
-                            .unwrap_or("unknown");
-                        let tool_name = match collab_tool {
-                            "spawn_agent" => "SpawnAgent",
-                            "send_input" => "SendInput",
-                            "wait" => "WaitForAgents",
-                            "close_agent" => "CloseAgent",
-                            _ => collab_tool,
-                        };
                         let tool_id = if item_id.is_empty() {
                             Uuid::new_v4().to_string()
                         } else {
@@ -1816,10 +3109,11 @@ pub fn parse_codex_run_to_message(
                         };
                         tool_calls.push(ToolCall {
                             id: tool_id.clone(),
-                            name: tool_name.to_string(),
+                            name: codex_item_to_tool_name(item_type, item),
                             input: item.clone(),
                             output: None,
                             parent_tool_use_id: None,
+                            full_output_path: None,
                         });
                         content_blocks.push(ContentBlock::ToolUse {
                             tool_call_id: tool_id.clone(),
@@ -1830,24 +3124,10 @@ pub fn parse_codex_run_to_message(
                     }
                     // Codex todo/plan list (history)
                     "todo_list" => {
-                        let tool_id = if item_id.is_empty() {
-                            Uuid::new_v4().to_string()
-                        } else {
-                            item_id.to_string()
-                        };
-                        tool_calls.push(ToolCall {
-                            id: tool_id.clone(),
-                            name: "CodexTodoList".to_string(),
-                            input: item.clone(),
-                            output: None,
-                            parent_tool_use_id: None,
-                        });
-                        content_blocks.push(ContentBlock::ToolUse {
-                            tool_call_id: tool_id.clone(),
-                        });
-                        if !item_id.is_empty() {
-                            pending_tool_ids.insert(item_id.to_string(), tool_id);
-                        }
+                        upsert_todo_block(&mut content_blocks, item_id, todo_items(item));
+                    }
+                    "agent_message" | "reasoning" => {
+                        block_slots.reserve(&mut content_blocks, item_id);
                     }
                     _ => {}
                 }
@@ -1864,9 +3144,13 @@ pub fn parse_codex_run_to_message(
                                 continue;
                             }
                             content.push_str(text);
-                            content_blocks.push(ContentBlock::Text {
-                                text: text.to_string(),
-                            });
+                            block_slots.fill(
+                                &mut content_blocks,
+                                item_id,
+                                ContentBlock::Text {
+                                    text: text.to_string(),
+                                },
+                            );
                         }
                     }
                     "command_execution" => {
@@ -1877,28 +3161,47 @@ pub fn parse_codex_run_to_message(
                             .to_string();
                         let tool_id = pending_tool_ids.remove(item_id).unwrap_or_default();
                         if !tool_id.is_empty() {
+                            // The run log keeps the full text; cap what reaches the UI
+                            let (output, full_output_path) =
+                                tool_output_limit.apply(&tool_id, output);
                             if let Some(tc) = tool_calls.iter_mut().find(|t| t.id == tool_id) {
                                 tc.output = Some(output);
+                                tc.full_output_path = full_output_path;
+                            }
+                            if let Some(message) = tool_failure_message(item) {
+                                content_blocks.push(ContentBlock::Error {
+                                    tool_call_id: tool_id,
+                                    message,
+                                });
                             }
                         }
                     }
                     "file_change" => {
-                        let changes = item
-                            .get("changes")
-                            .map(|v| serde_json::to_string(v).unwrap_or_default())
-                            .unwrap_or_default();
+                        let summary = FileChangeSummary::from_item(item);
+                        summary.record_applied(&mut changed_files);
                         let tool_id = pending_tool_ids.remove(item_id).unwrap_or_default();
                         if !tool_id.is_empty() {
                             if let Some(tc) = tool_calls.iter_mut().find(|t| t.id == tool_id) {
-                                tc.output = Some(changes);
+                                tc.output = Some(summary.output());
+                            }
+                            if let Some(message) = summary
+                                .rejection_message()
+                                .or_else(|| tool_failure_message(item))
+                            {
+                                content_blocks.push(ContentBlock::Error {
+                                    tool_call_id: tool_id,
+                                    message,
+                                });
                             }
                         }
                     }
                     "reasoning" => {
-                        if let Some(text) = item.get("text").and_then(|v| v.as_str()) {
-                            content_blocks.push(ContentBlock::Thinking {
-                                thinking: text.to_string(),
-                            });
+                        if let Some(text) = reasoning_item_text(item) {
+                            block_slots.fill(
+                                &mut content_blocks,
+                                item_id,
+                                ContentBlock::Thinking { thinking: text },
+                            );
                         }
                     }
                     "mcp_tool_call" => {
@@ -1960,6 +3263,9 @@ pub fn parse_codex_run_to_message(
                             }
                         }
                     }
+                    "todo_list" => {
+                        upsert_todo_block(&mut content_blocks, item_id, todo_items(item));
+                    }
                     _ => {}
                 }
             }
@@ -1970,17 +3276,15 @@ pub fn parse_codex_run_to_message(
                 let item_id = item.get("id").and_then(|v| v.as_str()).unwrap_or("");
 
                 if item_type == "todo_list" {
-                    if let Some(tool_id) = pending_tool_ids.get(item_id) {
-                        if let Some(tc) = tool_calls.iter_mut().find(|t| t.id == *tool_id) {
-                            tc.input = item.clone();
-                        }
-                    }
+                    upsert_todo_block(&mut content_blocks, item_id, todo_items(item));
                 }
             }
             _ => {}
         }
     }
 
+    block_slots.finish(&mut content_blocks);
+
     Ok(ChatMessage {
         id: run
             .assistant_message_id
@@ -2000,6 +3304,7 @@ pub fn parse_codex_run_to_message(
         effort_level: None,
         recovered: run.recovered,
         usage: run.usage.clone(),
+        changed_files,
     })
 }
 
@@ -2007,6 +3312,79 @@ pub fn parse_codex_run_to_message(
 // One-shot Codex execution (for magic prompts with --output-schema)
 // =============================================================================
 
+/// Whether `codex exec` needs `--skip-git-repo-check` to run in `working_dir`.
+///
+/// True when there is no working dir or it isn't inside a git repository
+/// (no `.git` dir or worktree file in it or any ancestor).
+pub fn needs_skip_git_check(working_dir: Option<&std::path::Path>) -> bool {
+    match working_dir {
+        Some(dir) => !dir.ancestors().any(|d| d.join(".git").exists()),
+        None => true,
+    }
+}
+
+/// Normalize a model for `build_one_shot_exec_args`, splitting off the fast
+/// suffix: "gpt5.4-fast" → ("gpt-5.4", true).
+pub fn one_shot_model(model: &str) -> (String, bool) {
+    let model = normalize_model(&Backend::Codex, model);
+    let (actual_model, is_fast) = split_fast_model(&model);
+    (actual_model.to_string(), is_fast)
+}
+
+/// Build `codex exec` args for a one-shot `--output-schema` call.
+///
+/// `model` is already normalized with any fast suffix split off into `is_fast`.
+/// The prompt is read from stdin (`-`). `skip_git_check` emits
+/// `--skip-git-repo-check` so Codex runs in folders that aren't git repos.
+pub fn build_one_shot_exec_args(
+    model: &str,
+    is_fast: bool,
+    output_schema_file: &std::path::Path,
+    working_dir: Option<&std::path::Path>,
+    skip_git_check: bool,
+    model_provider: Option<&str>,
+) -> Vec<std::ffi::OsString> {
+    let mut args: Vec<std::ffi::OsString> = ["exec", "--json", "--model", model, "--full-auto"]
+        .into_iter()
+        .map(std::ffi::OsString::from)
+        .collect();
+    if is_fast {
+        args.push("-c".into());
+        args.push("service_tier=\"fast\"".into());
+    }
+    if let Some(provider) = model_provider {
+        args.push("-c".into());
+        args.push(format!("model_provider={}", toml_string(provider)).into());
+    }
+    args.push("--output-schema".into());
+    args.push(output_schema_file.into());
+    if let Some(dir) = working_dir {
+        args.push("--cd".into());
+        args.push(dir.into());
+    }
+    if skip_git_check {
+        args.push("--skip-git-repo-check".into());
+    }
+    args.push("-".into()); // Read prompt from stdin
+    args
+}
+
+/// Quote a value as a TOML basic string for `codex -c key=value` overrides.
+fn toml_string(value: &str) -> String {
+    let escaped = value.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("\"{escaped}\"")
+}
+
+/// Custom Codex provider (a `model_providers` entry in the user's Codex config).
+/// Blank means Codex's default (OpenAI).
+pub fn model_provider_pref(app: &tauri::AppHandle) -> Option<String> {
+    crate::load_preferences_sync(app)
+        .ok()
+        .and_then(|p| p.codex_model_provider)
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+}
+
 /// Execute a one-shot Codex CLI call with `--output-schema` for structured JSON output.
 ///
 /// Equivalent to Claude's `--json-schema` pattern but for Codex:
@@ -2027,8 +3405,7 @@ pub fn execute_one_shot_codex(
         return Err("Codex CLI not installed".to_string());
     }
 
-    // Split fast suffix: "gpt-5.4-fast" → model="gpt-5.4" + service_tier="fast"
-    let (actual_model, is_fast) = split_fast_model(model);
+    let (actual_model, is_fast) = one_shot_model(model);
 
     log::info!(
         "Executing one-shot Codex CLI: model={actual_model}, fast={is_fast}, working_dir={:?}, reasoning_effort={:?}",
@@ -2043,20 +3420,14 @@ pub fn execute_one_shot_codex(
         .map_err(|e| format!("Failed to write schema file: {e}"))?;
 
     let mut cmd = crate::platform::silent_command(&cli_path);
-    cmd.args(["exec", "--json", "--model", actual_model, "--full-auto"]);
-    if is_fast {
-        cmd.args(["-c", "service_tier=\"fast\""]);
-    }
-    cmd.arg("--output-schema");
-    cmd.arg(&schema_file);
-    if let Some(dir) = working_dir {
-        cmd.arg("--cd");
-        cmd.arg(dir);
-    } else {
-        // One-shot calls that don't know a repository path should still run.
-        cmd.arg("--skip-git-repo-check");
-    }
-    cmd.arg("-"); // Read prompt from stdin
+    cmd.args(build_one_shot_exec_args(
+        &actual_model,
+        is_fast,
+        &schema_file,
+        working_dir,
+        needs_skip_git_check(working_dir),
+        model_provider_pref(app).as_deref(),
+    ));
     cmd.stdin(std::process::Stdio::piped())
         .stdout(std::process::Stdio::piped())
         .stderr(std::process::Stdio::piped());
@@ -2167,6 +3538,50 @@ mod tests {
     use super::*;
     use crate::chat::types::{RunEntry, RunStatus};
 
+    /// Completed run with no optional metadata, for the history parser tests
+    fn test_run_entry() -> RunEntry {
+        RunEntry {
+            run_id: "run-1".to_string(),
+            user_message_id: "user-1".to_string(),
+            user_message: "prompt".to_string(),
+            model: None,
+            execution_mode: None,
+            thinking_level: None,
+            effort_level: None,
+            started_at: 1,
+            ended_at: Some(2),
+            status: RunStatus::Completed,
+            assistant_message_id: Some("assistant-1".to_string()),
+            cancelled: false,
+            recovered: false,
+            claude_session_id: None,
+            pid: None,
+            usage: None,
+            cli_path: None,
+            cli_version: None,
+        }
+    }
+
+    /// No output cap, so the history parser tests never touch the filesystem
+    fn no_output_limit() -> ToolOutputLimit {
+        ToolOutputLimit::new(0, std::path::Path::new("run-1.jsonl"))
+    }
+
+    #[test]
+    fn thread_persistence_follows_the_start_response() {
+        let saved = serde_json::json!({ "thread": { "id": "t1", "path": "/tmp/rollout.jsonl" } });
+        assert!(thread_is_persisted(&saved));
+        let legacy = serde_json::json!({ "thread": { "id": "t1" } });
+        assert!(thread_is_persisted(&legacy));
+
+        let no_rollout = serde_json::json!({ "thread": { "id": "t1", "path": null } });
+        assert!(!thread_is_persisted(&no_rollout));
+        let ephemeral = serde_json::json!({
+            "thread": { "id": "t1", "ephemeral": true, "path": "/tmp/rollout.jsonl" }
+        });
+        assert!(!thread_is_persisted(&ephemeral));
+    }
+
     #[test]
     fn gpt_5_4_fast_enables_fast_service_tier() {
         let params = build_thread_start_params(
@@ -2177,11 +3592,304 @@ mod tests {
             None,
             false,
             None,
+            None,
+            None,
         );
         assert_eq!(params["model"], "gpt-5.4");
         assert_eq!(params["serviceTier"], "fast");
     }
 
+    #[test]
+    fn codex_item_tool_names_match_between_live_and_history() {
+        let mcp = serde_json::json!({ "server": "github", "tool": "search" });
+        assert_eq!(
+            codex_item_to_tool_name("mcp_tool_call", &mcp),
+            "mcp:github:search"
+        );
+        let spawn = serde_json::json!({ "tool": "spawn_agent" });
+        assert_eq!(
+            codex_item_to_tool_name("collab_tool_call", &spawn),
+            "SpawnAgent"
+        );
+        let none = serde_json::Value::Null;
+        assert_eq!(codex_item_to_tool_name("command_execution", &none), "Bash");
+        assert_eq!(
+            codex_item_to_tool_name("mcp_tool_call", &none),
+            "mcp:unknown:unknown"
+        );
+    }
+
+    #[test]
+    fn file_change_summary_splits_applied_and_rejected() {
+        let item = serde_json::json!({
+            "status": "completed",
+            "changes": [
+                { "path": "a.rs" },
+                { "path": "b.rs", "status": "failed" },
+            ],
+        });
+        let summary = FileChangeSummary::from_item(&item);
+        assert_eq!(summary.applied, vec!["a.rs"]);
+        assert_eq!(summary.rejected, vec!["b.rs"]);
+        assert_eq!(summary.output(), "Applied: a.rs\nRejected: b.rs");
+        assert_eq!(
+            summary.rejection_message().as_deref(),
+            Some("Changes to `b.rs` could not be applied")
+        );
+
+        let declined = serde_json::json!({ "status": "declined", "changes": [{ "path": "c.rs" }] });
+        assert_eq!(
+            FileChangeSummary::from_item(&declined).rejected,
+            vec!["c.rs"]
+        );
+    }
+
+    #[test]
+    fn file_changes_normalize_both_kind_shapes() {
+        let exec = serde_json::json!({
+            "changes": [
+                { "path": "new.rs", "kind": "add" },
+                { "path": "old.rs", "kind": "delete" },
+            ],
+        });
+        let server = serde_json::json!({
+            "changes": { "path": "a.rs", "kind": { "type": "update", "move_path": "b.rs" }, "diff": "@@" },
+        });
+
+        assert_eq!(
+            normalize_file_changes(&exec),
+            serde_json::json!([
+                { "path": "new.rs", "kind": "created" },
+                { "path": "old.rs", "kind": "deleted" },
+            ])
+        );
+        assert_eq!(
+            normalize_file_changes(&server),
+            serde_json::json!([
+                { "path": "a.rs", "kind": "modified", "move_path": "b.rs", "diff": "@@" },
+            ])
+        );
+    }
+
+    #[test]
+    fn dry_run_resolves_command_and_resume_requests() {
+        let dir = std::path::Path::new("/tmp/my repo");
+        let thread_params = build_thread_start_params(
+            dir,
+            Some("gpt-5.4"),
+            Some("build"),
+            false,
+            None,
+            false,
+            None,
+            None,
+            None,
+        );
+        let dry_run = build_codex_dry_run(
+            std::path::Path::new("/usr/local/bin/codex"),
+            vec![],
+            Some("thread-1"),
+            thread_params,
+            "hello",
+            dir,
+            Some("build"),
+            Some("high"),
+            &[],
+        );
+        assert_eq!(
+            dry_run.command,
+            "/usr/local/bin/codex app-server --listen stdio://"
+        );
+        assert_eq!(dry_run.requests[0]["method"], "thread/resume");
+        assert_eq!(dry_run.requests[0]["params"]["threadId"], "thread-1");
+        assert_eq!(dry_run.requests[0]["params"]["sandbox"], "workspace-write");
+        assert_eq!(dry_run.requests[1]["method"], "turn/start");
+        assert_eq!(dry_run.requests[1]["params"]["effort"], "high");
+        assert_eq!(shell_word("/tmp/my repo"), "'/tmp/my repo'");
+    }
+
+    #[test]
+    fn search_mode_maps_to_web_search_config() {
+        let params_for = |mode: SearchMode| {
+            build_thread_start_params(
+                std::path::Path::new("/tmp"),
+                None,
+                Some("plan"),
+                mode,
+                None,
+                false,
+                None,
+                None,
+                None,
+            )
+        };
+        assert_eq!(params_for(SearchMode::Live)["config"]["web_search"], "live");
+        assert_eq!(
+            params_for(SearchMode::default())["config"]["web_search"],
+            "cached"
+        );
+        assert_eq!(
+            params_for(SearchMode::Disabled)["config"]["web_search"],
+            "disabled"
+        );
+        assert_eq!(SearchMode::from(true), SearchMode::Live);
+        assert_eq!(SearchMode::from(false), SearchMode::Disabled);
+        assert_eq!(SearchMode::from_pref(""), SearchMode::Cached);
+    }
+
+    #[test]
+    fn reasoning_summary_is_only_set_when_requested() {
+        let params_for = |summary: Option<ReasonSummary>| {
+            build_thread_start_params(
+                std::path::Path::new("/tmp"),
+                None,
+                Some("plan"),
+                false,
+                None,
+                false,
+                None,
+                summary,
+                None,
+            )
+        };
+        assert!(params_for(None)["config"]
+            .get("model_reasoning_summary")
+            .is_none());
+        assert_eq!(
+            params_for(ReasonSummary::from_pref("concise"))["config"]["model_reasoning_summary"],
+            "concise"
+        );
+        assert_eq!(ReasonSummary::from_pref(""), None);
+    }
+
+    #[test]
+    fn one_shot_exec_args_skip_git_check_only_when_requested() {
+        let schema = std::path::Path::new("/tmp/schema.json");
+        let dir = std::path::Path::new("/tmp/scratch");
+
+        let with_skip = build_one_shot_exec_args("gpt-5.4", false, schema, Some(dir), true, None);
+        assert!(with_skip.iter().any(|a| a == "--skip-git-repo-check"));
+
+        let without_skip =
+            build_one_shot_exec_args("gpt-5.4", false, schema, Some(dir), false, None);
+        assert!(!without_skip.iter().any(|a| a == "--skip-git-repo-check"));
+        assert_eq!(without_skip.last().unwrap(), "-");
+    }
+
+    #[test]
+    fn one_shot_exec_args_request_fast_tier() {
+        let schema = std::path::Path::new("/tmp/schema.json");
+        let args = build_one_shot_exec_args("gpt-5.4", true, schema, None, false, None);
+        let pos = args
+            .iter()
+            .position(|a| a == "service_tier=\"fast\"")
+            .expect("fast tier override");
+        assert_eq!(args[pos - 1], "-c");
+        assert_eq!(args[3], "gpt-5.4");
+
+        let args = build_one_shot_exec_args("gpt-5.4", false, schema, None, false, None);
+        assert!(!args.iter().any(|a| a == "service_tier=\"fast\""));
+    }
+
+    #[test]
+    fn one_shot_exec_args_quote_model_provider() {
+        let schema = std::path::Path::new("/tmp/schema.json");
+        let args = build_one_shot_exec_args("gpt-5.4", false, schema, None, false, Some("azure"));
+        let pos = args
+            .iter()
+            .position(|a| a == "model_provider=\"azure\"")
+            .expect("provider override");
+        assert_eq!(args[pos - 1], "-c");
+
+        let args =
+            build_one_shot_exec_args("gpt-5.4", false, schema, None, false, Some(r#"a"b\c"#));
+        assert!(args.iter().any(|a| a == r#"model_provider="a\"b\\c""#));
+
+        let args = build_one_shot_exec_args("gpt-5.4", false, schema, None, false, None);
+        assert!(!args
+            .iter()
+            .any(|a| a.to_string_lossy().contains("model_provider")));
+    }
+
+    #[test]
+    fn needs_skip_git_check_detects_repos() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(needs_skip_git_check(None));
+        assert!(needs_skip_git_check(Some(dir.path())));
+
+        std::fs::create_dir(dir.path().join(".git")).unwrap();
+        let nested = dir.path().join("src");
+        std::fs::create_dir(&nested).unwrap();
+        assert!(!needs_skip_git_check(Some(dir.path())));
+        assert!(!needs_skip_git_check(Some(&nested)));
+    }
+
+    #[test]
+    fn context_percent_prefers_reported_window_and_last_request() {
+        let usage = serde_json::json!({
+            "inputTokens": 900_000,
+            "last": { "inputTokens": 200_000 },
+            "modelContextWindow": 250_000
+        });
+        assert_eq!(context_percent_used(&usage, None), Some((80, 250_000)));
+
+        let usage = serde_json::json!({ "inputTokens": 136_000 });
+        assert_eq!(
+            context_percent_used(&usage, codex_context_window("gpt-5.4-fast")),
+            Some((50, 272_000))
+        );
+        assert_eq!(
+            context_percent_used(&usage, codex_context_window("mystery")),
+            None
+        );
+    }
+
+    #[test]
+    fn context_window_lookup_accepts_model_aliases() {
+        assert_eq!(codex_context_window("gpt5"), Some(272_000));
+        assert_eq!(codex_context_window(" GPT5.4-fast "), Some(272_000));
+        assert_eq!(codex_context_window("codex"), Some(272_000));
+    }
+
+    #[test]
+    fn sandbox_denial_requires_a_failed_command() {
+        let denied = serde_json::json!({
+            "status": "failed",
+            "exit_code": 1,
+            "aggregated_output": "sandbox denied exec error, exit code: 1, stdout: , stderr: touch: foo: Operation not permitted",
+        });
+        assert!(is_sandbox_denial(&denied));
+
+        let succeeded = serde_json::json!({
+            "status": "completed",
+            "exit_code": 0,
+            "aggregated_output": "grep matched: permission denied",
+        });
+        assert!(!is_sandbox_denial(&succeeded));
+
+        let unrelated = serde_json::json!({
+            "exit_code": 2,
+            "aggregated_output": "ls: foo: No such file or directory",
+        });
+        assert!(!is_sandbox_denial(&unrelated));
+    }
+
+    #[test]
+    fn ordinary_permission_failures_are_not_sandbox_denials() {
+        for output in [
+            "chmod: changing permissions of 'x': Operation not permitted",
+            "npm ERR! Error: EACCES: permission denied, mkdir '/usr/lib/node_modules'",
+            "FAILED tests/test_sandbox.py::test_sandbox_teardown",
+        ] {
+            let failed = serde_json::json!({
+                "status": "failed",
+                "exit_code": 1,
+                "aggregated_output": output,
+            });
+            assert!(!is_sandbox_denial(&failed), "{output}");
+        }
+    }
+
     #[test]
     fn split_fast_model_recognises_gpt_5_4_fast() {
         assert_eq!(split_fast_model("gpt-5.4-fast"), ("gpt-5.4", true));
@@ -2209,11 +3917,140 @@ mod tests {
             None,
             false,
             None,
+            None,
+            None,
         );
         assert_eq!(params["model"], "gpt-5.3");
         assert!(params.get("serviceTier").is_none());
     }
 
+    #[test]
+    fn turn_finish_reason_prefers_explicit_reason() {
+        let turn = serde_json::json!({
+            "status": "completed",
+            "finishReason": "max_output_tokens",
+        });
+        assert_eq!(
+            turn_finish_reason(&turn).as_deref(),
+            Some("max_output_tokens")
+        );
+    }
+
+    #[test]
+    fn turn_finish_reason_maps_status() {
+        let completed = serde_json::json!({ "status": "completed" });
+        let interrupted = serde_json::json!({ "status": "interrupted" });
+        let failed = serde_json::json!({ "status": "failed" });
+        assert_eq!(turn_finish_reason(&completed).as_deref(), Some("stop"));
+        assert_eq!(
+            turn_finish_reason(&interrupted).as_deref(),
+            Some("cancelled")
+        );
+        assert_eq!(turn_finish_reason(&failed).as_deref(), Some("error"));
+        assert_eq!(turn_finish_reason(&serde_json::json!({})), None);
+    }
+
+    #[test]
+    fn unseen_text_returns_only_new_suffix() {
+        assert_eq!(unseen_text("Thinking hard", ""), "Thinking hard");
+        assert_eq!(unseen_text("Thinking hard", "Thinking"), " hard");
+        assert_eq!(unseen_text("Thinking hard", "Thinking hard"), "");
+        assert_eq!(unseen_text("Other text", "Summary"), "");
+    }
+
+    #[test]
+    fn reasoning_item_text_joins_app_server_parts() {
+        let exec_item = serde_json::json!({ "type": "reasoning", "text": "plain" });
+        assert_eq!(reasoning_item_text(&exec_item).as_deref(), Some("plain"));
+
+        let server_item = serde_json::json!({
+            "type": "reasoning",
+            "summary": ["first"],
+            "content": ["second"],
+        });
+        assert_eq!(
+            reasoning_item_text(&server_item).as_deref(),
+            Some("first\n\nsecond")
+        );
+        assert_eq!(
+            reasoning_item_text(&serde_json::json!({ "type": "reasoning" })),
+            None
+        );
+    }
+
+    #[test]
+    fn truncate_tool_output_caps_on_char_boundary() {
+        assert_eq!(truncate_tool_output("short", 10), ("short", 0));
+        assert_eq!(truncate_tool_output("abcdef", 0), ("abcdef", 0));
+        assert_eq!(truncate_tool_output("abcdef", 4), ("abcd", 2));
+        // "é" is two bytes; a cap landing inside it backs off
+        assert_eq!(truncate_tool_output("aéb", 2), ("a", 3));
+    }
+
+    #[test]
+    fn tool_output_limit_saves_full_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let limit = ToolOutputLimit::new(4, &dir.path().join("run-1.jsonl"));
+        assert_eq!(
+            limit.apply("tool-1", "tiny".to_string()),
+            ("tiny".to_string(), None)
+        );
+
+        let (output, full_output_path) = limit.apply("tool-1", "0123456789".to_string());
+        let path = dir.path().join("run-1.tool-1.output.txt");
+        assert_eq!(output, "0123\n...[truncated, 6 bytes omitted]");
+        assert_eq!(full_output_path, Some(path.to_string_lossy().to_string()));
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "0123456789");
+    }
+
+    #[test]
+    fn spill_file_name_sanitizes_item_ids() {
+        assert_eq!(
+            spill_file_name("run-1", "call_1-a"),
+            "run-1.call_1-a.output.txt"
+        );
+        assert_eq!(
+            spill_file_name("run-1", "../../etc/passwd"),
+            "run-1.______etc_passwd.output.txt"
+        );
+    }
+
+    #[test]
+    fn tool_progress_batches_output_within_interval() {
+        let mut progress = ToolProgress::default();
+        assert_eq!(progress.push("c1", "one\n").as_deref(), Some("one\n"));
+        assert_eq!(progress.push("c1", "two\n"), None);
+        assert_eq!(progress.push("c2", "other\n").as_deref(), Some("other\n"));
+
+        std::thread::sleep(TOOL_PROGRESS_INTERVAL);
+        assert_eq!(
+            progress.push("c1", "three\n").as_deref(),
+            Some("two\nthree\n")
+        );
+
+        progress.finish("c1");
+        assert_eq!(progress.push("c1", "four\n").as_deref(), Some("four\n"));
+    }
+
+    #[test]
+    fn duplicate_completed_agent_message_is_emitted_once() {
+        let mut messages = AgentMessages::default();
+        assert_eq!(messages.complete("m1", "Same text"), Some("Same text"));
+        assert_eq!(messages.complete("m1", "Same text"), None);
+
+        // A different item with the same short text is a real second message
+        assert_eq!(messages.complete("m2", "Done."), Some("Done."));
+        assert_eq!(messages.complete("m3", "Done."), Some("Done."));
+
+        // Deltas delivered only a prefix: emit just the tail
+        messages.push_delta("m4", "Intro. Same ");
+        assert_eq!(messages.complete("m4", "Intro. Same text"), Some("text"));
+
+        // Fully streamed: nothing left to emit, but the completion isn't a repeat
+        messages.push_delta("m5", "All of it");
+        assert_eq!(messages.complete("m5", "All of it"), Some(""));
+    }
+
     #[test]
     fn parse_cancelled_run_ignores_duplicate_completed_agent_message() {
         let lines = vec![
@@ -2223,25 +4060,14 @@ mod tests {
                 .to_string(),
         ];
         let run = RunEntry {
-            run_id: "run-1".to_string(),
-            user_message_id: "user-1".to_string(),
-            user_message: "prompt".to_string(),
-            model: None,
             execution_mode: Some("plan".to_string()),
-            thinking_level: None,
-            effort_level: None,
-            started_at: 1,
-            ended_at: Some(2),
             status: RunStatus::Cancelled,
-            assistant_message_id: Some("assistant-1".to_string()),
             cancelled: true,
-            recovered: false,
-            claude_session_id: None,
-            pid: None,
-            usage: None,
+            ..test_run_entry()
         };
 
-        let message = parse_codex_run_to_message(&lines, &run).expect("message");
+        let message =
+            parse_codex_run_to_message(&lines, &run, &no_output_limit()).expect("message");
 
         assert_eq!(message.content, "Same text");
         assert_eq!(
@@ -2251,6 +4077,207 @@ mod tests {
             }]
         );
     }
+
+    #[test]
+    fn parse_collects_applied_files_once() {
+        let lines: Vec<String> = [
+            r#"{"type":"item.completed","item":{"id":"f1","type":"file_change","changes":[{"path":"a.rs"},{"path":"b.rs","status":"failed"}]}}"#,
+            r#"{"type":"item.completed","item":{"id":"f2","type":"file_change","changes":[{"path":"a.rs"},{"path":"c.rs"}]}}"#,
+        ]
+        .iter()
+        .map(|l| l.to_string())
+        .collect();
+        let run = test_run_entry();
+
+        let message =
+            parse_codex_run_to_message(&lines, &run, &no_output_limit()).expect("message");
+
+        assert_eq!(message.changed_files, vec!["a.rs", "c.rs"]);
+    }
+
+    #[test]
+    fn parse_records_failed_command_as_error_block() {
+        let lines: Vec<String> = [
+            r#"{"type":"item.started","item":{"id":"c1","type":"command_execution","command":"cargo test"}}"#,
+            r#"{"type":"item.completed","item":{"id":"c1","type":"command_execution","aggregated_output":"boom","exit_code":101,"status":"failed"}}"#,
+            r#"{"type":"item.started","item":{"id":"c2","type":"command_execution","command":"ls"}}"#,
+            r#"{"type":"item.completed","item":{"id":"c2","type":"command_execution","aggregated_output":"a","exit_code":0,"status":"completed"}}"#,
+        ]
+        .iter()
+        .map(|l| l.to_string())
+        .collect();
+        let run = test_run_entry();
+
+        let message =
+            parse_codex_run_to_message(&lines, &run, &no_output_limit()).expect("message");
+
+        let errors: Vec<_> = message
+            .content_blocks
+            .iter()
+            .filter_map(|b| match b {
+                ContentBlock::Error {
+                    tool_call_id,
+                    message,
+                } => Some((tool_call_id.as_str(), message.as_str())),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(errors, vec![("c1", "Command exited with code 101")]);
+    }
+
+    #[test]
+    fn parse_keeps_final_todo_list_state() {
+        let lines: Vec<String> = [
+            r#"{"type":"item.started","item":{"id":"t1","type":"todo_list","items":[{"text":"Read","completed":false}]}}"#,
+            r#"{"type":"item.updated","item":{"id":"t1","type":"todo_list","items":[{"text":"Read","completed":false},{"text":"Fix","completed":false}]}}"#,
+            r#"{"type":"item.completed","item":{"id":"t1","type":"todo_list","items":[{"text":"Read","completed":true},{"text":"Fix","completed":true}]}}"#,
+        ]
+        .iter()
+        .map(|l| l.to_string())
+        .collect();
+        let run = test_run_entry();
+
+        let message =
+            parse_codex_run_to_message(&lines, &run, &no_output_limit()).expect("message");
+
+        assert!(message.tool_calls.is_empty());
+        let todo = |text: &str| TodoItem {
+            text: text.to_string(),
+            status: "completed".to_string(),
+        };
+        assert_eq!(
+            message.content_blocks,
+            vec![ContentBlock::Todo {
+                id: "t1".to_string(),
+                items: vec![todo("Read"), todo("Fix")],
+            }]
+        );
+    }
+
+    #[test]
+    fn todo_items_map_completion_to_status() {
+        let item = serde_json::json!({
+            "items": [
+                { "text": "Read", "completed": true },
+                { "text": "Fix", "completed": false },
+                { "completed": true }
+            ]
+        });
+        let statuses: Vec<_> = todo_items(&item)
+            .into_iter()
+            .map(|t| (t.text, t.status))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("Read".to_string(), "completed".to_string()),
+                ("Fix".to_string(), "pending".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn web_search_items_stream_as_tool_calls() {
+        let mut tool_calls = Vec::new();
+        let mut content_blocks = Vec::new();
+        let mut pending_tool_ids = HashMap::new();
+
+        let started = serde_json::json!({ "id": "ws_1", "type": "web_search", "query": "rust" });
+        let tool = start_info_tool(
+            "web_search",
+            &started,
+            &mut tool_calls,
+            &mut content_blocks,
+            &mut pending_tool_ids,
+        );
+        assert_eq!(tool.id, "ws_1");
+        assert_eq!(tool.name, "CodexWebSearch");
+        assert!(matches!(
+            content_blocks.as_slice(),
+            [ContentBlock::ToolUse { tool_call_id }] if tool_call_id == "ws_1"
+        ));
+
+        let completed =
+            serde_json::json!({ "id": "ws_1", "type": "web_search", "output": "3 results" });
+        let result = complete_info_tool(
+            "web_search",
+            &completed,
+            &mut tool_calls,
+            &mut pending_tool_ids,
+        );
+        assert_eq!(result, Some(("ws_1".to_string(), "3 results".to_string())));
+        assert_eq!(tool_calls.len(), 1);
+        assert_eq!(tool_calls[0].output.as_deref(), Some("3 results"));
+        assert!(pending_tool_ids.is_empty());
+    }
+
+    #[test]
+    fn parse_treats_repeated_item_started_as_update() {
+        let lines: Vec<String> = [
+            r#"{"type":"item.started","item":{"id":"c1","type":"command_execution","command":"ls"}}"#,
+            r#"{"type":"item.started","item":{"id":"c1","type":"command_execution","command":"ls -la"}}"#,
+            r#"{"type":"item.completed","item":{"id":"c1","type":"command_execution","aggregated_output":"a"}}"#,
+        ]
+        .iter()
+        .map(|l| l.to_string())
+        .collect();
+        let run = test_run_entry();
+
+        let message =
+            parse_codex_run_to_message(&lines, &run, &no_output_limit()).expect("message");
+
+        assert_eq!(message.tool_calls.len(), 1);
+        assert_eq!(message.tool_calls[0].input["command"], "ls -la");
+        assert_eq!(message.tool_calls[0].output.as_deref(), Some("a"));
+        assert_eq!(
+            message.content_blocks,
+            vec![ContentBlock::ToolUse {
+                tool_call_id: "c1".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_keeps_content_blocks_in_item_start_order() {
+        // Reasoning and text start before the tool but only complete after it
+        let lines: Vec<String> = [
+            r#"{"type":"item.started","item":{"id":"r1","type":"reasoning"}}"#,
+            r#"{"type":"item.started","item":{"id":"m1","type":"agent_message"}}"#,
+            r#"{"type":"item.started","item":{"id":"c1","type":"command_execution","command":"ls"}}"#,
+            r#"{"type":"item.completed","item":{"id":"r1","type":"reasoning","text":"Plan"}}"#,
+            r#"{"type":"item.completed","item":{"id":"c1","type":"command_execution","aggregated_output":"a"}}"#,
+            r#"{"type":"item.completed","item":{"id":"m1","type":"agent_message","text":"Listing"}}"#,
+            r#"{"type":"item.started","item":{"id":"m2","type":"agent_message"}}"#,
+            r#"{"type":"item.started","item":{"id":"r2","type":"reasoning"}}"#,
+            r#"{"type":"item.completed","item":{"id":"m2","type":"agent_message","text":"Done"}}"#,
+        ]
+        .iter()
+        .map(|l| l.to_string())
+        .collect();
+        let run = test_run_entry();
+
+        let message =
+            parse_codex_run_to_message(&lines, &run, &no_output_limit()).expect("message");
+
+        // r2 never completed, so its placeholder is dropped
+        assert_eq!(
+            message.content_blocks,
+            vec![
+                ContentBlock::Thinking {
+                    thinking: "Plan".to_string(),
+                },
+                ContentBlock::Text {
+                    text: "Listing".to_string(),
+                },
+                ContentBlock::ToolUse {
+                    tool_call_id: "c1".to_string(),
+                },
+                ContentBlock::Text {
+                    text: "Done".to_string(),
+                },
+            ]
+        );
+    }
 }
 
 /// Parse Codex NDJSON output to extract structured JSON from --output-schema response.
@@ -9,15 +9,17 @@
 
 use once_cell::sync::Lazy;
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::io::{BufRead, BufWriter, Write};
 use std::path::PathBuf;
 use std::process::{Child, ChildStdin, Stdio};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tauri::{AppHandle, Manager};
 
 use crate::codex_cli::resolve_cli_binary;
+use crate::http_server::EmitExt;
 use crate::platform::silent_command;
 
 // =============================================================================
@@ -35,6 +37,8 @@ pub enum ServerEvent {
         method: String,
         params: Value,
     },
+    /// Diagnostic line the server wrote to stderr that names this session's thread
+    Stderr(String),
     /// Server process died (EOF on stdout)
     ServerDied,
 }
@@ -61,12 +65,31 @@ struct CodexAppServerInner {
     server_dead: Arc<AtomicBool>,
 }
 
+/// Arguments used to launch the app-server (after the `codex` binary).
+pub const APP_SERVER_ARGS: [&str; 3] = ["app-server", "--listen", "stdio://"];
+
 // Global singleton
 static CODEX_SERVER: Lazy<Mutex<Option<CodexAppServerInner>>> = Lazy::new(|| Mutex::new(None));
 
 /// Cached AppHandle for PID file path resolution
 static APP_HANDLE: once_cell::sync::OnceCell<AppHandle> = once_cell::sync::OnceCell::new();
 
+/// Most recent stderr lines from the current server, for error reporting.
+static RECENT_STDERR: Lazy<Mutex<VecDeque<String>>> = Lazy::new(|| Mutex::new(VecDeque::new()));
+
+/// How many stderr lines `RECENT_STDERR` keeps.
+const RECENT_STDERR_LINES: usize = 20;
+
+/// How long the stdout reader waits on EOF for the stderr reader to finish, so
+/// the server's last words are in `RECENT_STDERR` before sessions hear it died.
+const STDERR_DRAIN_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Payload for `codex-server:stderr`: a server diagnostic not tied to any thread
+#[derive(serde::Serialize, Clone)]
+struct ServerStderrEvent {
+    line: String,
+}
+
 /// Number of active sessions using the server. When this drops to 0, a delayed
 /// shutdown is scheduled (matching the opencode server pattern).
 static USAGE_COUNT: AtomicU64 = AtomicU64::new(0);
@@ -92,6 +115,23 @@ fn pid_file_path() -> Option<PathBuf> {
         .map(|d| d.join("codex-app-server.pid"))
 }
 
+/// Stderr of the current server, kept out of the JSON-RPC stream.
+fn stderr_log_path() -> Option<PathBuf> {
+    APP_HANDLE
+        .get()
+        .and_then(|app| app.path().app_data_dir().ok())
+        .map(|d| d.join("codex-app-server.stderr.log"))
+}
+
+/// Last non-empty line the current server wrote to stderr, if any.
+pub fn last_stderr_line() -> Option<String> {
+    RECENT_STDERR
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .back()
+        .cloned()
+}
+
 fn write_pid_file(server_pid: u32) {
     let Some(path) = pid_file_path() else { return };
     let record = ServerPidRecord {
@@ -201,9 +241,7 @@ fn ensure_running_inner(app: &AppHandle) -> Result<(), String> {
     );
 
     let mut child = silent_command(&cli_path)
-        .arg("app-server")
-        .arg("--listen")
-        .arg("stdio://")
+        .args(APP_SERVER_ARGS)
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
@@ -228,14 +266,43 @@ fn ensure_running_inner(app: &AppHandle) -> Result<(), String> {
         .take()
         .ok_or("Failed to take stdout from app-server")?;
 
-    // Spawn stderr logger
+    let pending_requests: Arc<
+        Mutex<HashMap<u64, tokio::sync::oneshot::Sender<Result<Value, String>>>>,
+    > = Arc::new(Mutex::new(HashMap::new()));
+    let active_sessions: Arc<Mutex<HashMap<String, SessionContext>>> =
+        Arc::new(Mutex::new(HashMap::new()));
+    let server_dead = Arc::new(AtomicBool::new(false));
+
+    // Spawn stderr logger: writes to its own file and forwards each line to the
+    // session whose thread it names, or app-wide when it names none. Dropping
+    // `stderr_done_tx` on exit tells the stdout reader stderr is drained.
+    let (stderr_done_tx, stderr_done_rx) = std::sync::mpsc::channel::<()>();
     if let Some(stderr) = child.stderr.take() {
+        let as_ = active_sessions.clone();
         std::thread::spawn(move || {
+            let _done = stderr_done_tx;
+            let mut log_file = stderr_log_path().and_then(|p| std::fs::File::create(p).ok());
+            RECENT_STDERR
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .clear();
             let reader = std::io::BufReader::new(stderr);
             for line in reader.lines() {
                 match line {
                     Ok(l) if !l.trim().is_empty() => {
                         log::debug!("codex-app-server stderr: {l}");
+                        if let Some(ref mut f) = log_file {
+                            let _ = writeln!(f, "{l}");
+                        }
+                        {
+                            let mut recent =
+                                RECENT_STDERR.lock().unwrap_or_else(|e| e.into_inner());
+                            if recent.len() == RECENT_STDERR_LINES {
+                                recent.pop_front();
+                            }
+                            recent.push_back(l.clone());
+                        }
+                        forward_stderr_line(&as_, l);
                     }
                     _ => {}
                 }
@@ -243,19 +310,12 @@ fn ensure_running_inner(app: &AppHandle) -> Result<(), String> {
         });
     }
 
-    let pending_requests: Arc<
-        Mutex<HashMap<u64, tokio::sync::oneshot::Sender<Result<Value, String>>>>,
-    > = Arc::new(Mutex::new(HashMap::new()));
-    let active_sessions: Arc<Mutex<HashMap<String, SessionContext>>> =
-        Arc::new(Mutex::new(HashMap::new()));
-    let server_dead = Arc::new(AtomicBool::new(false));
-
     // Background reader thread
     let pr = pending_requests.clone();
     let as_ = active_sessions.clone();
     let dead = server_dead.clone();
     let reader_handle = std::thread::spawn(move || {
-        reader_loop(stdout, pr, as_, dead);
+        reader_loop(stdout, pr, as_, dead, stderr_done_rx);
     });
 
     let server = CodexAppServerInner {
@@ -490,6 +550,7 @@ fn reader_loop(
     pending_requests: Arc<Mutex<HashMap<u64, tokio::sync::oneshot::Sender<Result<Value, String>>>>>,
     active_sessions: Arc<Mutex<HashMap<String, SessionContext>>>,
     server_dead: Arc<AtomicBool>,
+    stderr_done: std::sync::mpsc::Receiver<()>,
 ) {
     let reader = std::io::BufReader::new(stdout);
 
@@ -558,6 +619,10 @@ fn reader_loop(
     log::warn!("Codex app-server stdout EOF — server died");
     server_dead.store(true, Ordering::SeqCst);
 
+    // Let the stderr reader catch up so `last_stderr_line` reflects the exit.
+    // Disconnects as soon as it finishes; the timeout covers a stderr held open.
+    let _ = stderr_done.recv_timeout(STDERR_DRAIN_TIMEOUT);
+
     // Notify all active sessions
     let sessions = active_sessions.lock().unwrap();
     for (_tid, ctx) in sessions.iter() {
@@ -573,6 +638,40 @@ fn reader_loop(
     remove_pid_file();
 }
 
+/// Send a stderr line to the active session whose thread id it contains. Lines
+/// naming no thread go out once as `codex-server:stderr` rather than to every
+/// session, so one conversation's diagnostics never show up in another's.
+fn forward_stderr_line(
+    active_sessions: &Arc<Mutex<HashMap<String, SessionContext>>>,
+    line: String,
+) {
+    // A panicked session thread must not take stderr forwarding down with it.
+    let sessions = active_sessions.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(ctx) = sessions
+        .iter()
+        .find(|(thread_id, _)| mentions_thread(&line, thread_id))
+        .map(|(_, ctx)| ctx)
+    {
+        let _ = ctx.event_tx.send(ServerEvent::Stderr(line));
+    } else if let Some(app) = APP_HANDLE.get() {
+        let _ = app.emit_all("codex-server:stderr", &ServerStderrEvent { line });
+    }
+}
+
+/// Whether `line` names `thread_id` as a whole token, e.g. `thread_id=<id>` or
+/// `"threadId":"<id>"`, not as part of a longer id.
+fn mentions_thread(line: &str, thread_id: &str) -> bool {
+    if thread_id.is_empty() {
+        return false;
+    }
+    let is_id_char = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+    line.match_indices(thread_id).any(|(start, _)| {
+        let before = line[..start].chars().next_back();
+        let after = line[start + thread_id.len()..].chars().next();
+        !before.is_some_and(is_id_char) && !after.is_some_and(is_id_char)
+    })
+}
+
 /// Route a server notification to the appropriate session by threadId.
 fn route_notification(
     active_sessions: &Arc<Mutex<HashMap<String, SessionContext>>>,
@@ -632,3 +731,27 @@ fn route_server_request(
         log::warn!("Approval request without threadId: {method}");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mentions_thread_matches_delimited_ids_only() {
+        let id = "019a-thread";
+        assert!(mentions_thread(
+            "session_loop{thread_id=019a-thread}: failed",
+            id
+        ));
+        assert!(mentions_thread(
+            r#"{"threadId":"019a-thread","error":"x"}"#,
+            id
+        ));
+        assert!(mentions_thread("019a-thread", id));
+
+        assert!(!mentions_thread("thread_id=019a-thread-2: failed", id));
+        assert!(!mentions_thread("thread_id=x019a-thread", id));
+        assert!(!mentions_thread("no thread here", id));
+        assert!(!mentions_thread("anything", ""));
+    }
+}
@@ -472,6 +472,7 @@ pub async fn regenerate_session_name(
         custom_profile_name,
         backend_override,
         reasoning_effort,
+        opencode_server_key: None,
     };
 
     spawn_naming_task(app, request);
@@ -1261,6 +1262,20 @@ pub async fn set_sessions_last_opened_bulk(
 // Chat Commands (now session-based)
 // ============================================================================
 
+/// Why `session`'s Codex thread must not be resumed from `worktree_path`, if it
+/// mustn't. Codex threads are bound to the directory they started in; resuming
+/// one from a moved or recreated worktree would run against stale paths.
+fn stale_codex_thread(session: Option<&Session>, worktree_path: &str) -> Option<String> {
+    let session = session?;
+    let thread_id = session.codex_thread_id.as_deref()?;
+    let thread_cwd = session.codex_thread_cwd.as_deref()?;
+    (thread_cwd != worktree_path).then(|| {
+        format!(
+            "Codex thread {thread_id} was started in {thread_cwd}, but this worktree is now at {worktree_path}. Starting a new conversation."
+        )
+    })
+}
+
 /// Send a message to Claude and get a response
 ///
 /// This command:
@@ -1412,6 +1427,10 @@ pub async fn send_chat_message(
                         .clone(),
                     backend_override: prefs.magic_prompt_backends.session_naming_backend.clone(),
                     reasoning_effort: prefs.magic_prompt_efforts.session_naming_effort.clone(),
+                    opencode_server_key: Some(crate::opencode_server::ServerKey::resolve(
+                        prefs.opencode_server_per_project,
+                        worktree_record.as_ref().map(|w| w.project_id.as_str()),
+                    )),
                 };
 
                 // Spawn in background - does not block chat
@@ -1530,12 +1549,31 @@ pub async fn send_chat_message(
     let claude_session_id = sessions
         .find_session(&session_id)
         .and_then(|s| s.claude_session_id.clone());
-    let codex_thread_id = sessions
+    let mut codex_thread_id = sessions
         .find_session(&session_id)
         .and_then(|s| s.codex_thread_id.clone());
+    if effective_backend == Backend::Codex {
+        if let Some(message) =
+            stale_codex_thread(sessions.find_session(&session_id), &worktree_path)
+        {
+            log::warn!("{message}");
+            let _ = app.emit_all(
+                "chat:warning",
+                &super::opencode::WarningEvent {
+                    session_id: session_id.clone(),
+                    worktree_id: worktree_id.clone(),
+                    message,
+                },
+            );
+            codex_thread_id = None;
+        }
+    }
     let opencode_session_id = sessions
         .find_session(&session_id)
         .and_then(|s| s.opencode_session_id.clone());
+    let opencode_server_url = sessions
+        .find_session(&session_id)
+        .and_then(|s| s.server_url.clone());
 
     // Start NDJSON run log for crash recovery
     let mut run_log_writer = run_log::start_run(
@@ -1559,6 +1597,17 @@ pub async fn send_chat_message(
         Some(effective_backend.clone()),
     )?;
 
+    // Record which CLI binary serves this run, for support and reproducibility
+    let run_cli_path = match effective_backend {
+        Backend::Claude => resolve_cli_binary(&app),
+        Backend::Codex => crate::codex_cli::resolve_cli_binary(&app),
+        Backend::Opencode => crate::opencode_cli::resolve_cli_binary(&app),
+    };
+    let cli_version = run_log::cli_version(&run_cli_path);
+    if let Err(e) = run_log_writer.set_cli(&run_cli_path, cli_version) {
+        log::warn!("Failed to record CLI for run: {e}");
+    }
+
     // Get file paths for detached execution
     let input_file = run_log_writer.input_file_path()?;
     let output_file = run_log_writer.output_file_path()?;
@@ -1575,11 +1624,13 @@ pub async fn send_chat_message(
 
     // Inject web tools in plan mode if preference is enabled
     // Claude: add WebFetch/WebSearch to allowed tools
-    // Codex: set search_enabled flag for --search
+    // Codex: enable web_search using the preferred mode
     let mut final_allowed_tools = allowed_tools.unwrap_or_default();
-    let mut codex_search_enabled = false;
+    let mut codex_search_mode = super::codex::SearchMode::Disabled;
     let mut codex_multi_agent_enabled = false;
     let mut codex_max_agent_threads: Option<u32> = None;
+    let mut codex_reasoning_summary: Option<super::codex::ReasonSummary> = None;
+    let mut codex_model_provider: Option<String> = None;
     if execution_mode.as_deref() == Some("plan") {
         if let Ok(prefs) = crate::load_preferences(app.clone()).await {
             if prefs.allow_web_tools_in_plan_mode {
@@ -1589,20 +1640,29 @@ pub async fn send_chat_message(
                         final_allowed_tools.push("WebSearch".to_string());
                     }
                     Backend::Codex => {
-                        codex_search_enabled = true;
+                        codex_search_mode =
+                            super::codex::SearchMode::from_pref(&prefs.codex_web_search_mode);
                     }
                     Backend::Opencode => {}
                 }
             }
         }
     }
-    // Read Codex multi-agent preferences
+    // Read Codex multi-agent, reasoning summary and provider preferences
     if effective_backend == Backend::Codex {
         if let Ok(prefs) = crate::load_preferences(app.clone()).await {
             codex_multi_agent_enabled = prefs.codex_multi_agent_enabled;
             if codex_multi_agent_enabled {
                 codex_max_agent_threads = Some(prefs.codex_max_agent_threads.clamp(1, 8));
             }
+            codex_reasoning_summary = prefs
+                .codex_reasoning_summary
+                .as_deref()
+                .and_then(super::codex::ReasonSummary::from_pref);
+            codex_model_provider = prefs
+                .codex_model_provider
+                .map(|p| p.trim().to_string())
+                .filter(|p| !p.is_empty());
         }
     }
     let allowed_tools_for_cli = if final_allowed_tools.is_empty() {
@@ -1623,6 +1683,10 @@ pub async fn send_chat_message(
         error_emitted: bool,
         usage: Option<super::types::UsageData>,
         backend: Backend,
+        /// OpenCode server URL the run talked to (OpenCode only)
+        server_url: Option<String>,
+        /// Files the turn edited (Codex only)
+        changed_files: Vec<String>,
     }
 
     // Execute CLI in detached mode on a dedicated OS thread.
@@ -1637,6 +1701,7 @@ pub async fn send_chat_message(
     let thread_claude_session_id = claude_session_id.clone();
     let thread_codex_thread_id = codex_thread_id.clone();
     let thread_opencode_session_id = opencode_session_id.clone();
+    let thread_opencode_server_url = opencode_server_url.clone();
     let thread_model = model.clone();
     let thread_execution_mode = execution_mode.clone();
     let thread_thinking_level = thinking_level.clone();
@@ -1648,9 +1713,11 @@ pub async fn send_chat_message(
     let thread_custom_profile = custom_profile_name.clone();
     let thread_message = message.clone();
     let thread_backend = effective_backend.clone();
-    let thread_codex_search = codex_search_enabled;
+    let thread_codex_search = codex_search_mode;
     let thread_codex_multi_agent = codex_multi_agent_enabled;
     let thread_codex_max_threads = codex_max_agent_threads;
+    let thread_codex_reasoning_summary = codex_reasoning_summary;
+    let thread_codex_model_provider = codex_model_provider.clone();
 
     // For OpenCode sessions: create a cancel flag so we can signal the blocking HTTP thread.
     // Register it before spawning so cancel_process can find it immediately.
@@ -1763,6 +1830,8 @@ pub async fn send_chat_message(
                                     error_emitted: false,
                                     usage: response.usage,
                                     backend: Backend::Claude,
+                                    server_url: None,
+                                    changed_files: Vec::new(),
                                 },
                             ));
                         }
@@ -2109,6 +2178,8 @@ pub async fn send_chat_message(
                     codex_instructions_file.as_deref(),
                     thread_codex_multi_agent,
                     thread_codex_max_threads,
+                    thread_codex_reasoning_summary,
+                    thread_codex_model_provider.as_deref(),
                 ) {
                     Ok(response) => Ok((
                         0, // No PID for app-server sessions
@@ -2121,6 +2192,8 @@ pub async fn send_chat_message(
                             error_emitted: response.error_emitted,
                             usage: response.usage,
                             backend: Backend::Codex,
+                            server_url: None,
+                            changed_files: response.changed_files,
                         },
                     )),
                     Err(e) => {
@@ -2140,11 +2213,24 @@ pub async fn send_chat_message(
                         super::types::EffortLevel::Off => None,
                     });
 
+                // Read once: both the server key and the system prompt need them.
+                let thread_prefs = crate::load_preferences_sync(&thread_app).ok();
+                let thread_projects =
+                    crate::projects::storage::load_projects_data(&thread_app).ok();
+                let server_key = crate::opencode_server::ServerKey::resolve(
+                    thread_prefs
+                        .as_ref()
+                        .is_some_and(|p| p.opencode_server_per_project),
+                    thread_projects
+                        .as_ref()
+                        .and_then(|data| data.find_worktree(&thread_worktree_id))
+                        .map(|w| w.project_id.as_str()),
+                );
+
                 let system_prompt = {
                     use crate::projects::github_issues::{
                         get_github_contexts_dir, get_session_issue_refs, get_session_pr_refs,
                     };
-                    use crate::projects::storage::load_projects_data;
 
                     let mut system_prompt_parts: Vec<String> = Vec::new();
 
@@ -2157,22 +2243,13 @@ pub async fn send_chat_message(
                     }
 
                     // Global system prompt from preferences
-                    if let Ok(prefs_path) = crate::get_preferences_path(&thread_app) {
-                        if let Ok(contents) = std::fs::read_to_string(&prefs_path) {
-                            if let Ok(prefs) =
-                                serde_json::from_str::<crate::AppPreferences>(&contents)
-                            {
-                                if let Some(prompt) = prefs
-                                    .magic_prompts
-                                    .global_system_prompt
-                                    .as_deref()
-                                    .map(|s| s.trim())
-                                    .filter(|s| !s.is_empty())
-                                {
-                                    system_prompt_parts.push(prompt.to_string());
-                                }
-                            }
-                        }
+                    if let Some(prompt) = thread_prefs
+                        .as_ref()
+                        .and_then(|prefs| prefs.magic_prompts.global_system_prompt.as_deref())
+                        .map(|s| s.trim())
+                        .filter(|s| !s.is_empty())
+                    {
+                        system_prompt_parts.push(prompt.to_string());
                     }
 
                     // Parallel execution prompt
@@ -2184,7 +2261,7 @@ pub async fn send_chat_message(
                     }
 
                     // Per-project custom system prompt
-                    if let Ok(data) = load_projects_data(&thread_app) {
+                    if let Some(data) = &thread_projects {
                         if let Some(worktree) = data.find_worktree(&thread_worktree_id) {
                             if let Some(project) = data.find_project(&worktree.project_id) {
                                 if let Some(prompt) = &project.custom_system_prompt {
@@ -2340,8 +2417,10 @@ pub async fn send_chat_message(
                     &thread_app,
                     &thread_session_id,
                     &thread_worktree_id,
+                    &server_key,
                     std::path::Path::new(&thread_working_dir),
                     thread_opencode_session_id.as_deref(),
+                    thread_opencode_server_url.as_deref(),
                     thread_model.as_deref(),
                     thread_execution_mode.as_deref(),
                     opencode_reasoning_effort.as_deref(),
@@ -2363,6 +2442,8 @@ pub async fn send_chat_message(
                             error_emitted: false,
                             usage: response.usage,
                             backend: Backend::Opencode,
+                            server_url: response.server_url,
+                            changed_files: Vec::new(),
                         },
                     )),
                     Err(e) => {
@@ -2505,6 +2586,7 @@ pub async fn send_chat_message(
     let has_tool_calls = !unified_response.tool_calls.is_empty();
     let resume_id_for_log = unified_response.resume_id.clone();
     let response_backend = unified_response.backend.clone();
+    let response_server_url = unified_response.server_url.clone();
 
     // Handle error_emitted: backend emitted chat:error during execution (e.g., Codex usage limit).
     // Treat like undo_send so the user message doesn't persist in history.
@@ -2529,6 +2611,7 @@ pub async fn send_chat_message(
             effort_level: None,
             recovered: false,
             usage: None,
+            changed_files: vec![],
         });
     }
 
@@ -2555,9 +2638,11 @@ pub async fn send_chat_message(
                         }
                         Backend::Codex => {
                             session.codex_thread_id = Some(resume_id_for_log.clone());
+                            session.codex_thread_cwd = Some(worktree_path.clone());
                         }
                         Backend::Opencode => {
                             session.opencode_session_id = Some(resume_id_for_log.clone());
+                            session.server_url = response_server_url.clone();
                         }
                     }
                 }
@@ -2592,6 +2677,7 @@ pub async fn send_chat_message(
             effort_level: None,
             recovered: false,
             usage: None,
+            changed_files: vec![],
         });
     }
 
@@ -2628,6 +2714,7 @@ pub async fn send_chat_message(
         effort_level: None,
         recovered: false,
         usage: unified_response.usage.clone(),
+        changed_files: unified_response.changed_files,
     };
     // Note: Assistant message is stored in NDJSON, not sessions JSON.
     // Messages are loaded from NDJSON on demand via load_session_messages().
@@ -2673,9 +2760,11 @@ pub async fn send_chat_message(
                     }
                     Backend::Codex => {
                         session.codex_thread_id = Some(resume_id_for_log.clone());
+                        session.codex_thread_cwd = Some(worktree_path.clone());
                     }
                     Backend::Opencode => {
                         session.opencode_session_id = Some(resume_id_for_log.clone());
+                        session.server_url = response_server_url.clone();
                     }
                 }
             }
@@ -2756,7 +2845,9 @@ pub async fn clear_session_history(
             session.messages.clear();
             session.claude_session_id = None;
             session.codex_thread_id = None;
+            session.codex_thread_cwd = None;
             session.opencode_session_id = None;
+            session.server_url = None;
             session.selected_model = selected_model;
             session.selected_thinking_level = selected_thinking_level;
             session.selected_provider = selected_provider;
@@ -2875,6 +2966,76 @@ pub async fn cancel_chat_message(
     cancel_process(&app, &session_id, &worktree_id)
 }
 
+/// Resolve the Codex invocation a chat message would use, without running it
+///
+/// Returns the `codex app-server` command line, its Codex-related environment
+/// and the JSON-RPC requests for the turn, so users can reproduce a misbehaving
+/// run by hand. Session context files and custom instructions are not included.
+#[allow(clippy::too_many_arguments)]
+#[tauri::command]
+pub async fn preview_codex_command(
+    app: AppHandle,
+    session_id: String,
+    worktree_id: String,
+    worktree_path: String,
+    message: String,
+    model: Option<String>,
+    execution_mode: Option<String>,
+    reasoning_effort: Option<String>,
+) -> Result<super::codex::CodexDryRun, String> {
+    log::trace!("Previewing Codex command for session: {session_id}");
+    let sessions = load_sessions(&app, &worktree_path, &worktree_id)?;
+    let session = sessions.find_session(&session_id);
+    // Preview what send_chat_message would do, which starts fresh in this case
+    let codex_thread_id = session
+        .filter(|_| stale_codex_thread(session, &worktree_path).is_none())
+        .and_then(|s| s.codex_thread_id.clone());
+    let prefs = crate::load_preferences(app.clone()).await?;
+
+    let search_mode =
+        if execution_mode.as_deref() == Some("plan") && prefs.allow_web_tools_in_plan_mode {
+            super::codex::SearchMode::from_pref(&prefs.codex_web_search_mode)
+        } else {
+            super::codex::SearchMode::Disabled
+        };
+    let model_provider = prefs
+        .codex_model_provider
+        .as_deref()
+        .map(str::trim)
+        .filter(|p| !p.is_empty());
+    let working_dir = std::path::Path::new(&worktree_path);
+    let thread_params = super::codex::build_thread_start_params(
+        working_dir,
+        model.as_deref(),
+        execution_mode.as_deref(),
+        search_mode,
+        None,
+        prefs.codex_multi_agent_enabled,
+        prefs
+            .codex_multi_agent_enabled
+            .then(|| prefs.codex_max_agent_threads.clamp(1, 8)),
+        prefs
+            .codex_reasoning_summary
+            .as_deref()
+            .and_then(super::codex::ReasonSummary::from_pref),
+        model_provider,
+    );
+
+    let dry_run = super::codex::build_codex_dry_run(
+        &crate::codex_cli::resolve_cli_binary(&app),
+        super::codex::codex_env_for_dry_run(),
+        codex_thread_id.as_deref(),
+        thread_params,
+        &message,
+        working_dir,
+        execution_mode.as_deref(),
+        reasoning_effort.as_deref(),
+        &[],
+    );
+    log::info!("Codex dry run: {}", dry_run.command);
+    Ok(dry_run)
+}
+
 /// Check if any sessions have running Claude processes
 /// Used for quit confirmation dialog to prevent accidental closure during active sessions
 #[tauri::command]
@@ -3907,6 +4068,32 @@ pub async fn save_context_file(
     })
 }
 
+/// Read the untruncated output of a tool call (`ToolCall.full_output_path`)
+///
+/// Validates that the path is a saved tool output within the chat data directory.
+#[tauri::command]
+pub async fn read_tool_output(app: AppHandle, path: String) -> Result<String, String> {
+    log::trace!("Reading full tool output: {path}");
+
+    let data_dir_canonical = get_data_dir(&app)?
+        .canonicalize()
+        .map_err(|e| format!("Failed to canonicalize data dir: {e}"))?;
+    let file_path_canonical = std::path::PathBuf::from(&path)
+        .canonicalize()
+        .map_err(|e| format!("Failed to canonicalize file path: {e}"))?;
+
+    let is_tool_output = file_path_canonical
+        .file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| n.ends_with(super::codex::TOOL_OUTPUT_SUFFIX));
+    if !is_tool_output || !file_path_canonical.starts_with(&data_dir_canonical) {
+        return Err("Invalid tool output path".to_string());
+    }
+
+    std::fs::read_to_string(&file_path_canonical)
+        .map_err(|e| format!("Failed to read tool output: {e}"))
+}
+
 /// Read a saved context file content
 ///
 /// Validates that the path is within the session-context directory.
@@ -4631,6 +4818,8 @@ pub async fn get_session_debug_info(
                     status: run.status.clone(),
                     user_message_preview: preview,
                     usage: run.usage.clone(),
+                    cli_path: run.cli_path.clone(),
+                    cli_version: run.cli_version.clone(),
                 });
             }
         }
@@ -4805,6 +4994,7 @@ pub async fn resume_session(
                     &worktree_id_clone,
                     &output_file,
                     pid,
+                    super::tail::TailConfig::for_backend(&Backend::Claude),
                 ) {
                     Ok(response) => (response.session_id, response.usage, response.cancelled),
                     Err(e) => {
@@ -5707,4 +5897,18 @@ my-disabled: /usr/bin/disabled (STDIO) - disabled";
         let selected = find_neighbor_non_archived_session_id(&remaining, 0);
         assert_eq!(selected.as_deref(), Some("s3"));
     }
+
+    #[test]
+    fn test_stale_codex_thread_only_when_cwd_moved() {
+        let mut session = Session::new("Session 1".to_string(), 0, Backend::Codex);
+        session.codex_thread_id = Some("thread-1".to_string());
+        assert_eq!(stale_codex_thread(Some(&session), "/wt"), None);
+
+        session.codex_thread_cwd = Some("/wt".to_string());
+        assert_eq!(stale_codex_thread(Some(&session), "/wt"), None);
+
+        let message = stale_codex_thread(Some(&session), "/moved").unwrap();
+        assert!(message.contains("thread-1") && message.contains("/moved"));
+        assert_eq!(stale_codex_thread(None, "/moved"), None);
+    }
 }
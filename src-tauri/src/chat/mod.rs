@@ -3,6 +3,7 @@ pub(crate) mod codex;
 pub(crate) mod codex_server;
 mod commands;
 pub mod detached;
+pub mod models;
 mod naming;
 pub(crate) mod opencode;
 pub mod registry;
@@ -20,10 +21,38 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 static ACTIVE_TAILER_COUNT: once_cell::sync::Lazy<AtomicUsize> =
     once_cell::sync::Lazy::new(|| AtomicUsize::new(0));
 
-pub fn increment_tailer_count() {
-    ACTIVE_TAILER_COUNT.fetch_add(1, Ordering::Relaxed);
+/// One reserved tailer slot, released when dropped (including on early error returns).
+pub struct TailerSlot(());
+
+impl Drop for TailerSlot {
+    fn drop(&mut self) {
+        ACTIVE_TAILER_COUNT.fetch_sub(1, Ordering::SeqCst);
+    }
 }
 
-pub fn decrement_tailer_count() {
-    ACTIVE_TAILER_COUNT.fetch_sub(1, Ordering::Relaxed);
+/// Reserve a tailer slot, rejecting the run when `max_concurrent_tailers` sessions
+/// are already streaming, so large parallel workloads can't exhaust threads and
+/// file handles. The check and the increment are one atomic step, so concurrent
+/// sends can't both take the last slot.
+pub fn ensure_tailer_capacity(app: &tauri::AppHandle) -> Result<TailerSlot, String> {
+    let limit = crate::load_preferences_sync(app)
+        .map(|p| p.max_concurrent_tailers)
+        .unwrap_or(0);
+    let mut active = ACTIVE_TAILER_COUNT.load(Ordering::SeqCst);
+    loop {
+        if limit > 0 && active >= limit as usize {
+            return Err(format!(
+                "Too many concurrent sessions ({active} streaming, limit {limit}). Wait for one to finish or raise the limit in Settings."
+            ));
+        }
+        match ACTIVE_TAILER_COUNT.compare_exchange_weak(
+            active,
+            active + 1,
+            Ordering::SeqCst,
+            Ordering::SeqCst,
+        ) {
+            Ok(_) => return Ok(TailerSlot(())),
+            Err(current) => active = current,
+        }
+    }
 }
@@ -0,0 +1,73 @@
+//! Model id normalization shared by all backends
+//!
+//! Users type model names in several places ("Sonnet", "gpt5-codex", ...).
+//! Each CLI expects its own canonical ids, so aliases are mapped here before
+//! the id reaches a backend. Unknown models pass through unchanged.
+
+use super::types::Backend;
+
+/// Map a user-facing model name to the canonical id the backend's CLI expects.
+///
+/// A trailing `-fast` marker is preserved so the backends can still split it
+/// off into their fast-mode settings.
+pub fn normalize_model(backend: &Backend, model: &str) -> String {
+    let trimmed = model.trim();
+    let (base, fast_suffix) = match trimmed.strip_suffix("-fast") {
+        Some(base) => (base, "-fast"),
+        None => (trimmed, ""),
+    };
+
+    let canonical = match backend {
+        Backend::Claude => match base.to_ascii_lowercase().as_str() {
+            "sonnet" | "claude-sonnet" => "sonnet".to_string(),
+            "opus" | "claude-opus" => "opus".to_string(),
+            "haiku" | "claude-haiku" => "haiku".to_string(),
+            _ => base.to_string(),
+        },
+        Backend::Codex => match base.to_ascii_lowercase().as_str() {
+            "gpt5" => "gpt-5".to_string(),
+            "codex" | "gpt5-codex" => "gpt-5-codex".to_string(),
+            "gpt5.4" => "gpt-5.4".to_string(),
+            _ => base.to_string(),
+        },
+        // OpenCode ids are provider/model pairs; case matters to providers.
+        Backend::Opencode => base.to_string(),
+    };
+
+    format!("{canonical}{fast_suffix}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_aliases_to_canonical_ids() {
+        assert_eq!(normalize_model(&Backend::Claude, " Sonnet "), "sonnet");
+        assert_eq!(
+            normalize_model(&Backend::Claude, "claude-opus-fast"),
+            "opus-fast"
+        );
+        assert_eq!(
+            normalize_model(&Backend::Codex, "gpt5-codex"),
+            "gpt-5-codex"
+        );
+        assert_eq!(
+            normalize_model(&Backend::Codex, "gpt5.4-fast"),
+            "gpt-5.4-fast"
+        );
+    }
+
+    #[test]
+    fn passes_unknown_models_through() {
+        assert_eq!(
+            normalize_model(&Backend::Claude, "claude-sonnet-4-5-20250929"),
+            "claude-sonnet-4-5-20250929"
+        );
+        assert_eq!(normalize_model(&Backend::Codex, "o3"), "o3");
+        assert_eq!(
+            normalize_model(&Backend::Opencode, "anthropic/Claude-Sonnet"),
+            "anthropic/Claude-Sonnet"
+        );
+    }
+}
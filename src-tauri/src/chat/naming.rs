@@ -35,6 +35,8 @@ pub struct NamingRequest {
     pub backend_override: Option<String>,
     /// Per-operation reasoning effort override (None = use default)
     pub reasoning_effort: Option<String>,
+    /// OpenCode server already resolved by the caller (None = resolve from the worktree)
+    pub opencode_server_key: Option<crate::opencode_server::ServerKey>,
 }
 
 /// Successful session rename result (for event emission)
@@ -660,18 +662,22 @@ fn generate_names_opencode_inner(
     model: &str,
     request: &NamingRequest,
 ) -> Result<NamingOutput, String> {
-    let base_url = crate::opencode_server::acquire(app)?;
+    let server_key = request.opencode_server_key.clone().unwrap_or_else(|| {
+        crate::opencode_server::ServerKey::for_worktree(app, &request.worktree_id)
+    });
+    let base_url = crate::opencode_server::acquire(app, &server_key)?;
 
-    struct ServerReleaseGuard;
+    struct ServerReleaseGuard(crate::opencode_server::ServerKey);
     impl Drop for ServerReleaseGuard {
         fn drop(&mut self) {
-            crate::opencode_server::release();
+            crate::opencode_server::release(&self.0);
         }
     }
-    let _server_guard = ServerReleaseGuard;
+    let _server_guard = ServerReleaseGuard(server_key);
 
     let client = reqwest::blocking::Client::builder()
         .timeout(std::time::Duration::from_secs(300))
+        .default_headers(crate::opencode_server::auth_headers())
         .build()
         .map_err(|e| format!("Failed to build OpenCode HTTP client: {e}"))?;
 
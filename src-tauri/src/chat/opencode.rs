@@ -1,6 +1,7 @@
 //! OpenCode HTTP execution engine (opencode serve).
 
-use super::types::{ContentBlock, ToolCall, UsageData};
+use super::tail::TailConfig;
+use super::types::{Backend, ContentBlock, ToolCall, UsageData};
 use crate::http_server::EmitExt;
 use base64::{engine::general_purpose::STANDARD, Engine};
 use regex::Regex;
@@ -65,6 +66,23 @@ pub struct ErrorEvent {
     pub error: String,
 }
 
+/// Non-fatal problem the user should know about (the run continues)
+#[derive(serde::Serialize, Clone)]
+pub struct WarningEvent {
+    pub session_id: String,
+    pub worktree_id: String,
+    pub message: String,
+}
+
+/// Append the memory-limit explanation to a request failure when the server
+/// was killed by its limit mid-prompt.
+fn explain_server_exit(key: &crate::opencode_server::ServerKey, error: String) -> String {
+    match crate::opencode_server::take_memory_limit_exit(key) {
+        Some(reason) => format!("{error}. {reason}"),
+        None => error,
+    }
+}
+
 /// Response from OpenCode execution.
 pub struct OpenCodeResponse {
     pub content: String,
@@ -73,6 +91,8 @@ pub struct OpenCodeResponse {
     pub content_blocks: Vec<ContentBlock>,
     pub cancelled: bool,
     pub usage: Option<UsageData>,
+    /// Server URL the request was sent to
+    pub server_url: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -452,6 +472,7 @@ fn spawn_sse_listener(
     done_flag: Arc<AtomicBool>,
     cancelled: Arc<AtomicBool>,
     sse_ready_tx: std::sync::mpsc::SyncSender<bool>,
+    tail_config: TailConfig,
 ) -> Arc<AtomicBool> {
     let sse_active = Arc::new(AtomicBool::new(false));
     let sse_active_clone = sse_active.clone();
@@ -482,6 +503,7 @@ fn spawn_sse_listener(
                 cancelled,
                 sse_active_clone,
                 sse_ready_tx,
+                tail_config,
             ));
         })
         .ok(); // Detach — don't join
@@ -502,8 +524,13 @@ async fn sse_listener_loop(
     cancelled: Arc<AtomicBool>,
     sse_active: Arc<AtomicBool>,
     sse_ready_tx: std::sync::mpsc::SyncSender<bool>,
+    tail_config: TailConfig,
 ) {
-    let client = match reqwest::Client::builder().no_proxy().build() {
+    let client = match reqwest::Client::builder()
+        .no_proxy()
+        .default_headers(crate::opencode_server::auth_headers())
+        .build()
+    {
         Ok(c) => c,
         Err(e) => {
             log::warn!("OpenCode SSE: failed to build async client: {e}");
@@ -565,7 +592,7 @@ async fn sse_listener_loop(
     let mut poll_count: u64 = 0;
     let mut tracked_parts: HashMap<String, TrackedPartState> = HashMap::new();
     let mut last_activity = tokio::time::Instant::now();
-    let drain_window = Duration::from_millis(750);
+    let drain_window = tail_config.drain_window;
 
     log::info!(
         "OpenCode SSE: listening for events (opencode_session={opencode_session_id}, directory={working_dir})"
@@ -600,9 +627,9 @@ async fn sse_listener_loop(
             biased;
             c = response.chunk() => c,
             _ = tokio::time::sleep(if draining {
-                Duration::from_millis(100)
+                tail_config.poll_interval_fast
             } else {
-                Duration::from_millis(500)
+                tail_config.poll_interval
             }) => {
                 if draining && last_activity.elapsed() >= drain_window {
                     log::info!(
@@ -1095,8 +1122,10 @@ pub fn execute_opencode_http(
     app: &tauri::AppHandle,
     session_id: &str,
     worktree_id: &str,
+    server_key: &crate::opencode_server::ServerKey,
     working_dir: &std::path::Path,
     existing_opencode_session_id: Option<&str>,
+    existing_server_url: Option<&str>,
     model: Option<&str>,
     execution_mode: Option<&str>,
     reasoning_effort: Option<&str>,
@@ -1113,24 +1142,76 @@ pub fn execute_opencode_http(
             content_blocks: vec![],
             cancelled: true,
             usage: None,
+            server_url: existing_server_url.map(str::to_string),
         });
     }
 
-    let base_url = crate::opencode_server::acquire(app)?;
+    // Held until this function returns, covering the prompt and the SSE stream
+    let _tailer_slot = match super::ensure_tailer_capacity(app) {
+        Ok(slot) => slot,
+        Err(error_msg) => {
+            log::warn!("{error_msg}");
+            let _ = app.emit_all(
+                "chat:error",
+                &ErrorEvent {
+                    session_id: session_id.to_string(),
+                    worktree_id: worktree_id.to_string(),
+                    error: error_msg.clone(),
+                },
+            );
+            return Err(error_msg);
+        }
+    };
+
+    let base_url = crate::opencode_server::acquire(app, server_key)?;
 
     // RAII guard: decrements the server usage count when this function exits.
     // The server only shuts down when the last consumer releases.
-    struct ServerReleaseGuard;
+    struct ServerReleaseGuard(crate::opencode_server::ServerKey);
     impl Drop for ServerReleaseGuard {
         fn drop(&mut self) {
-            crate::opencode_server::release();
+            crate::opencode_server::release(&self.0);
+        }
+    }
+    let _server_guard = ServerReleaseGuard(server_key.clone());
+
+    // The previous server for this key may have been killed by its memory limit.
+    if let Some(message) = crate::opencode_server::take_memory_limit_exit(server_key) {
+        let _ = app.emit_all(
+            "chat:warning",
+            &WarningEvent {
+                session_id: session_id.to_string(),
+                worktree_id: worktree_id.to_string(),
+                message,
+            },
+        );
+    }
+
+    // OpenCode sessions live on the server that created them; resuming against a
+    // different server (e.g. after a port or external URL change) will not find it.
+    if let (Some(existing), Some(expected_url)) =
+        (existing_opencode_session_id, existing_server_url)
+    {
+        if expected_url != base_url {
+            let message = format!(
+                "OpenCode session {existing} was created on {expected_url}, but the current server is {base_url}. Resuming may fail."
+            );
+            log::warn!("{message}");
+            let _ = app.emit_all(
+                "chat:warning",
+                &WarningEvent {
+                    session_id: session_id.to_string(),
+                    worktree_id: worktree_id.to_string(),
+                    message,
+                },
+            );
         }
     }
-    let _server_guard = ServerReleaseGuard;
 
     // 30 min timeout — OpenCode agentic tasks can run for extended periods
     let client = reqwest::blocking::Client::builder()
         .timeout(std::time::Duration::from_secs(1800))
+        .default_headers(crate::opencode_server::auth_headers())
         .build()
         .map_err(|e| format!("Failed to build OpenCode HTTP client: {e}"))?;
 
@@ -1171,7 +1252,11 @@ pub fn execute_opencode_http(
 
     // Update the cancel flag registry with the OpenCode session ID so that
     // cancel_process() can send a server-side interrupt request.
-    super::registry::update_cancel_flag_session_id(session_id, opencode_session_id.clone());
+    super::registry::update_cancel_flag_session_id(
+        session_id,
+        opencode_session_id.clone(),
+        base_url.clone(),
+    );
 
     let selected_model = if let Some(pm) = parse_provider_model(model) {
         pm
@@ -1210,12 +1295,14 @@ pub fn execute_opencode_http(
             content_blocks: vec![],
             cancelled: true,
             usage: None,
+            server_url: Some(base_url),
         });
     }
 
     // --- SSE streaming: spawn a background listener before sending POST ---
     let done_flag = Arc::new(AtomicBool::new(false));
     let (sse_ready_tx, sse_ready_rx) = std::sync::mpsc::sync_channel::<bool>(1);
+    let tail_config = TailConfig::for_backend(&Backend::Opencode);
 
     let sse_active = spawn_sse_listener(
         app.clone(),
@@ -1227,11 +1314,12 @@ pub fn execute_opencode_http(
         done_flag.clone(),
         cancelled.clone(),
         sse_ready_tx,
+        tail_config,
     );
 
-    // Wait up to 3 seconds for SSE to signal ready (connected or failed)
+    // Wait for SSE to signal ready (connected or failed)
     let sse_connected = sse_ready_rx
-        .recv_timeout(std::time::Duration::from_secs(3))
+        .recv_timeout(tail_config.connect_grace)
         .unwrap_or(false);
 
     if sse_connected {
@@ -1269,9 +1357,16 @@ pub fn execute_opencode_http(
                 .query(&query)
                 .json(&payload)
                 .send()
-                .map_err(|e| format!("Failed to send OpenCode message: {e}"))?
+                .map_err(|e| {
+                    explain_server_exit(server_key, format!("Failed to send OpenCode message: {e}"))
+                })?
+        }
+        Err(e) => {
+            return Err(explain_server_exit(
+                server_key,
+                format!("Failed to send OpenCode message: {e}"),
+            ))
         }
-        Err(e) => return Err(format!("Failed to send OpenCode message: {e}")),
     };
 
     if !response.status().is_success() {
@@ -1386,6 +1481,7 @@ pub fn execute_opencode_http(
                     input: input.clone(),
                     output: None,
                     parent_tool_use_id: None,
+                    full_output_path: None,
                 });
                 content_blocks.push(ContentBlock::ToolUse {
                     tool_call_id: tool_call_id.clone(),
@@ -1475,6 +1571,7 @@ pub fn execute_opencode_http(
             content_blocks,
             cancelled: true,
             usage,
+            server_url: Some(base_url),
         });
     }
 
@@ -1495,6 +1592,7 @@ pub fn execute_opencode_http(
         content_blocks,
         cancelled: false,
         usage,
+        server_url: Some(base_url),
     })
 }
 
@@ -1533,7 +1631,8 @@ pub fn execute_one_shot_opencode(
     // on a dedicated OS thread to avoid panicking reqwest::blocking inside
     // the Tokio async runtime that Tauri async commands use.
     let handle = std::thread::spawn(move || {
-        let base_url = crate::opencode_server::acquire(&app)?;
+        let server_key = crate::opencode_server::ServerKey::Shared;
+        let base_url = crate::opencode_server::acquire(&app, &server_key)?;
         let result = one_shot_opencode_blocking(
             &base_url,
             &prompt,
@@ -1541,8 +1640,9 @@ pub fn execute_one_shot_opencode(
             schema_value.as_ref(),
             &dir,
             reasoning.as_deref(),
-        );
-        crate::opencode_server::release();
+        )
+        .map_err(|e| explain_server_exit(&server_key, e));
+        crate::opencode_server::release(&server_key);
         result
     });
 
@@ -1562,6 +1662,7 @@ fn one_shot_opencode_blocking(
 ) -> Result<String, String> {
     let client = reqwest::blocking::Client::builder()
         .timeout(std::time::Duration::from_secs(600))
+        .default_headers(crate::opencode_server::auth_headers())
         .build()
         .map_err(|e| format!("Failed to build OpenCode HTTP client: {e}"))?;
 
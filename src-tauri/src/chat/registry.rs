@@ -23,9 +23,11 @@ static PENDING_CANCELS: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(H
 
 /// Cancel flags for OpenCode sessions (HTTP-based, no PID to kill).
 /// When cancel is requested, the flag is set so the blocking HTTP thread can detect it.
-/// Stores (cancel_flag, optional_opencode_session_id) — the session ID is set after
-/// the OpenCode session is created, enabling server-side interrupt on cancel.
-static CANCEL_FLAGS: Lazy<Mutex<HashMap<String, (Arc<AtomicBool>, Option<String>)>>> =
+/// Stores (cancel_flag, optional (opencode_session_id, server_base_url)) — the pair
+/// is set after the OpenCode session is created, enabling server-side interrupt on cancel.
+/// The base URL is kept because each project may use its own server.
+type OpenCodeCancelEntry = (Arc<AtomicBool>, Option<(String, String)>);
+static CANCEL_FLAGS: Lazy<Mutex<HashMap<String, OpenCodeCancelEntry>>> =
     Lazy::new(|| Mutex::new(HashMap::new()));
 
 /// Codex app-server turn registry: maps session_id → (thread_id, turn_id).
@@ -117,13 +119,17 @@ pub fn register_cancel_flag(session_id: String, flag: Arc<AtomicBool>) -> bool {
     true
 }
 
-/// Update the OpenCode session ID for a registered cancel flag.
+/// Update the OpenCode session ID and server URL for a registered cancel flag.
 /// Called after the OpenCode session is created so that `cancel_process` can
 /// send a server-side interrupt request.
-pub fn update_cancel_flag_session_id(session_id: &str, opencode_session_id: String) {
+pub fn update_cancel_flag_session_id(
+    session_id: &str,
+    opencode_session_id: String,
+    base_url: String,
+) {
     let mut flags = lock_recover(&CANCEL_FLAGS, "CANCEL_FLAGS");
     if let Some(entry) = flags.get_mut(session_id) {
-        entry.1 = Some(opencode_session_id);
+        entry.1 = Some((opencode_session_id, base_url));
     }
 }
 
@@ -306,34 +312,31 @@ pub fn cancel_process(
             .get(session_id)
             .cloned()
     };
-    if let Some((flag, opencode_session_id)) = flag_entry {
+    if let Some((flag, opencode_session)) = flag_entry {
         // OpenCode session: set the cancel flag so the HTTP thread detects it
         log::warn!("OpenCode session {session_id}: setting cancel flag");
         flag.store(true, Ordering::SeqCst);
 
         // Fire-and-forget: call the OpenCode interrupt endpoint to abort server-side processing.
         // This makes the in-flight blocking POST return immediately.
-        if let Some(oc_sid) = opencode_session_id {
-            if let Some(base_url) = crate::opencode_server::get_current_url() {
-                let interrupt_url = format!("{base_url}/session/{oc_sid}/interrupt");
-                std::thread::spawn(move || {
-                    log::info!("OpenCode: sending interrupt to {interrupt_url}");
-                    let client = reqwest::blocking::Client::builder()
-                        .timeout(std::time::Duration::from_secs(5))
-                        .build();
-                    match client {
-                        Ok(c) => match c.post(&interrupt_url).send() {
-                            Ok(resp) => {
-                                log::info!("OpenCode interrupt response: status={}", resp.status())
-                            }
-                            Err(e) => log::warn!("OpenCode interrupt request failed: {e}"),
-                        },
-                        Err(e) => log::warn!("OpenCode interrupt client build failed: {e}"),
-                    }
-                });
-            } else {
-                log::warn!("OpenCode: no server URL available for interrupt");
-            }
+        if let Some((oc_sid, base_url)) = opencode_session {
+            let interrupt_url = format!("{base_url}/session/{oc_sid}/interrupt");
+            std::thread::spawn(move || {
+                log::info!("OpenCode: sending interrupt to {interrupt_url}");
+                let client = reqwest::blocking::Client::builder()
+                    .timeout(std::time::Duration::from_secs(5))
+                    .default_headers(crate::opencode_server::auth_headers())
+                    .build();
+                match client {
+                    Ok(c) => match c.post(&interrupt_url).send() {
+                        Ok(resp) => {
+                            log::info!("OpenCode interrupt response: status={}", resp.status())
+                        }
+                        Err(e) => log::warn!("OpenCode interrupt request failed: {e}"),
+                    },
+                    Err(e) => log::warn!("OpenCode interrupt client build failed: {e}"),
+                }
+            });
         }
 
         // Mark run as cancelled immediately (before HTTP call returns)
@@ -439,25 +442,24 @@ pub fn cancel_process_if_running(
             .get(session_id)
             .cloned()
     };
-    if let Some((flag, opencode_session_id)) = flag_entry {
+    if let Some((flag, opencode_session)) = flag_entry {
         // OpenCode session actively running — set the cancel flag
         log::trace!("OpenCode session {session_id} is running, setting cancel flag");
         flag.store(true, Ordering::SeqCst);
 
         // Fire-and-forget interrupt
-        if let Some(oc_sid) = opencode_session_id {
-            if let Some(base_url) = crate::opencode_server::get_current_url() {
-                let interrupt_url = format!("{base_url}/session/{oc_sid}/interrupt");
-                std::thread::spawn(move || {
-                    log::info!("OpenCode: sending interrupt to {interrupt_url}");
-                    let client = reqwest::blocking::Client::builder()
-                        .timeout(std::time::Duration::from_secs(5))
-                        .build();
-                    if let Ok(c) = client {
-                        let _ = c.post(&interrupt_url).send();
-                    }
-                });
-            }
+        if let Some((oc_sid, base_url)) = opencode_session {
+            let interrupt_url = format!("{base_url}/session/{oc_sid}/interrupt");
+            std::thread::spawn(move || {
+                log::info!("OpenCode: sending interrupt to {interrupt_url}");
+                let client = reqwest::blocking::Client::builder()
+                    .timeout(std::time::Duration::from_secs(5))
+                    .default_headers(crate::opencode_server::auth_headers())
+                    .build();
+                if let Ok(c) = client {
+                    let _ = c.post(&interrupt_url).send();
+                }
+            });
         }
 
         if let Err(e) = run_log::mark_running_run_cancelled(app, session_id) {
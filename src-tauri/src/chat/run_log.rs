@@ -3,11 +3,14 @@
 //! This module handles writing and reading JSONL log files that contain
 //! the raw Claude CLI output. Each run (Claude execution) gets its own file.
 
+use std::collections::HashMap;
 use std::fs::{self, File, OpenOptions};
 use std::io::{BufRead, BufReader, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use once_cell::sync::Lazy;
 use uuid::Uuid;
 
 use super::storage::{
@@ -186,6 +189,30 @@ impl RunLogWriter {
         Ok(())
     }
 
+    /// Record the CLI binary (and its version, see `cli_version`) that serves this run
+    pub fn set_cli(&self, cli_path: &Path, version: Option<String>) -> Result<(), String> {
+        let run_id = self.run_id.clone();
+        let path = cli_path.to_string_lossy().to_string();
+
+        with_metadata_mut(
+            &self.app,
+            &self.session_id,
+            &self.worktree_id,
+            &self.session_name,
+            self.order,
+            |metadata| {
+                if let Some(run) = metadata.find_run_mut(&run_id) {
+                    run.cli_path = Some(path.clone());
+                    run.cli_version = version.clone();
+                }
+                Ok(())
+            },
+        )?;
+
+        log::trace!("Set CLI {path} ({version:?}) for run: {}", self.run_id);
+        Ok(())
+    }
+
     /// Get the path to the JSONL output file for this run
     pub fn output_file_path(&self) -> Result<PathBuf, String> {
         let session_dir = get_session_dir(&self.app, &self.session_id)?;
@@ -262,6 +289,46 @@ impl RunLogWriter {
     }
 }
 
+/// `--version` output per CLI binary. Filled when the CLI's installation is
+/// checked, or by a one-off background probe, so a send only does a lookup.
+static CLI_VERSIONS: Lazy<Mutex<HashMap<PathBuf, Option<String>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Record what `cli_path --version` printed, e.g. during an installation check.
+/// Replaces any earlier entry, so a check after an upgrade refreshes it.
+pub fn remember_cli_version(cli_path: &Path, output: &str) {
+    let version = Some(output.trim().to_string()).filter(|v| !v.is_empty());
+    if let Ok(mut cache) = CLI_VERSIONS.lock() {
+        cache.insert(cli_path.to_path_buf(), version);
+    }
+}
+
+/// Version recorded for a CLI binary. The first lookup of an unknown path
+/// probes it on a background thread and returns `None`, so that run records no
+/// version and later runs do.
+pub fn cli_version(cli_path: &Path) -> Option<String> {
+    let mut cache = CLI_VERSIONS.lock().ok()?;
+    if let Some(version) = cache.get(cli_path) {
+        return version.clone();
+    }
+    // Placeholder so concurrent sends don't each start a probe
+    cache.insert(cli_path.to_path_buf(), None);
+    drop(cache);
+
+    let path = cli_path.to_path_buf();
+    std::thread::spawn(move || {
+        let output = crate::platform::silent_command(&path)
+            .arg("--version")
+            .output()
+            .ok()
+            .filter(|o| o.status.success());
+        if let Some(output) = output {
+            remember_cli_version(&path, &String::from_utf8_lossy(&output.stdout));
+        }
+    });
+    None
+}
+
 /// Start a new run - creates JSONL file and updates metadata
 #[allow(clippy::too_many_arguments)]
 pub fn start_run(
@@ -327,8 +394,10 @@ pub fn start_run(
         cancelled: false,
         recovered: false,
         claude_session_id: None,
-        pid: None,   // Set later via set_pid() after spawning detached process
-        usage: None, // Set on completion via complete()
+        pid: None,      // Set later via set_pid() after spawning detached process
+        usage: None,    // Set on completion via complete()
+        cli_path: None, // Set via set_cli() once the backend binary is known
+        cli_version: None,
     };
 
     with_metadata_mut(
@@ -544,6 +613,7 @@ pub fn parse_run_to_message(lines: &[String], run: &RunEntry) -> Result<ChatMess
                                         input,
                                         output: None,
                                         parent_tool_use_id: current_parent_tool_use_id.clone(),
+                                        full_output_path: None,
                                     });
 
                                     content_blocks.push(ContentBlock::ToolUse { tool_call_id: id });
@@ -557,6 +627,34 @@ pub fn parse_run_to_message(lines: &[String], run: &RunEntry) -> Result<ChatMess
                                         });
                                     }
                                 }
+                                // Tool failures written by persist_partial_cancelled_content
+                                "error" => {
+                                    let field = |name: &str| {
+                                        block
+                                            .get(name)
+                                            .and_then(|v| v.as_str())
+                                            .unwrap_or("")
+                                            .to_string()
+                                    };
+                                    content_blocks.push(ContentBlock::Error {
+                                        tool_call_id: field("tool_call_id"),
+                                        message: field("message"),
+                                    });
+                                }
+                                // Codex todo lists, likewise
+                                "todo" => {
+                                    let id = block
+                                        .get("id")
+                                        .and_then(|v| v.as_str())
+                                        .unwrap_or("")
+                                        .to_string();
+                                    let items = block
+                                        .get("items")
+                                        .cloned()
+                                        .and_then(|v| serde_json::from_value(v).ok())
+                                        .unwrap_or_default();
+                                    content_blocks.push(ContentBlock::Todo { id, items });
+                                }
                                 _ => {}
                             }
                         }
@@ -664,6 +762,7 @@ pub fn parse_run_to_message(lines: &[String], run: &RunEntry) -> Result<ChatMess
         effort_level: None,
         recovered: run.recovered,
         usage: run.usage.clone(), // Token usage from metadata
+        changed_files: vec![],
     })
 }
 
@@ -692,6 +791,8 @@ pub fn load_session_messages(
     );
 
     let mut messages = Vec::new();
+    // Same cap as live Codex turns, so a reload shows what streaming showed
+    let tool_output_max_bytes = super::codex::tool_output_max_bytes(app);
 
     for run in &metadata.runs {
         // Skip user message for instant-cancelled runs (undo_send)
@@ -716,6 +817,7 @@ pub fn load_session_messages(
                 effort_level: run.effort_level.clone(),
                 recovered: false,
                 usage: None, // User messages don't have token usage
+                changed_files: vec![],
             });
         }
 
@@ -746,7 +848,11 @@ pub fn load_session_messages(
                 metadata.backend == Backend::Codex || metadata.backend == Backend::Opencode
             };
             let mut assistant_msg = if use_codex_parser {
-                super::codex::parse_codex_run_to_message(&lines, run)?
+                let tool_output_limit = super::codex::ToolOutputLimit::new(
+                    tool_output_max_bytes,
+                    &get_run_log_path(app, session_id, &run.run_id)?,
+                );
+                super::codex::parse_codex_run_to_message(&lines, run, &tool_output_limit)?
             } else {
                 parse_run_to_message(&lines, run)?
             };
@@ -911,6 +1017,19 @@ pub fn persist_partial_cancelled_content(
                 ContentBlock::Thinking { thinking } => {
                     serde_json::json!({"type": "thinking", "thinking": thinking})
                 }
+                ContentBlock::Error {
+                    tool_call_id,
+                    message,
+                } => {
+                    serde_json::json!({
+                        "type": "error",
+                        "tool_call_id": tool_call_id,
+                        "message": message
+                    })
+                }
+                ContentBlock::Todo { id, items } => {
+                    serde_json::json!({"type": "todo", "id": id, "items": items})
+                }
             })
             .collect();
 
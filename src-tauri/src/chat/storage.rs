@@ -664,7 +664,9 @@ pub fn load_sessions(
                 backend: super::commands::resolve_default_backend(app, Some(worktree_id)),
                 claude_session_id: None,
                 codex_thread_id: None,
+                codex_thread_cwd: None,
                 opencode_session_id: None,
+                server_url: None,
                 selected_model: None,
                 selected_thinking_level: None,
                 selected_provider: None,
@@ -746,7 +748,9 @@ where
                 backend: super::commands::resolve_default_backend(app, Some(worktree_id)),
                 claude_session_id: None,
                 codex_thread_id: None,
+                codex_thread_cwd: None,
                 opencode_session_id: None,
+                server_url: None,
                 selected_model: None,
                 selected_thinking_level: None,
                 selected_provider: None,
@@ -8,6 +8,8 @@ use std::io::{BufRead, BufReader, Seek, SeekFrom};
 use std::path::Path;
 use std::time::Duration;
 
+use super::types::Backend;
+
 /// Default (idle) polling interval for tailing NDJSON files.
 /// Used when the last poll returned no new lines.
 pub const POLL_INTERVAL: Duration = Duration::from_millis(50);
@@ -17,6 +19,73 @@ pub const POLL_INTERVAL: Duration = Duration::from_millis(50);
 /// instead of 50ms reduces per-event latency by up to 45ms.
 pub const POLL_INTERVAL_FAST: Duration = Duration::from_millis(5);
 
+/// Polling cadence and timeouts for following a backend's output.
+///
+/// Claude tails its NDJSON file. Codex streams over the app-server channel and
+/// only polls while creating its output file. OpenCode reads an SSE stream.
+/// Each backend reads its own timeouts; the others stay zero.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TailConfig {
+    /// Sleep between polls when the last poll returned no new output
+    pub poll_interval: Duration,
+    /// Sleep between polls while output is arriving (OpenCode: while draining)
+    pub poll_interval_fast: Duration,
+    /// Claude: how long to wait for the first output before giving up
+    pub startup_timeout: Duration,
+    /// Claude: how long a dead process is tolerated while the run starts
+    pub startup_grace: Duration,
+    /// Claude: once the process has exited, how long to keep reading trailing output
+    pub dead_process_timeout: Duration,
+    /// Codex: how long a turn may go without any event before it is abandoned
+    pub idle_timeout: Duration,
+    /// Codex: how long to retry creating the turn's output file
+    pub output_file_grace: Duration,
+    /// OpenCode: how long to wait for the SSE stream to connect
+    pub connect_grace: Duration,
+    /// OpenCode: once the session is idle, how long to keep reading trailing events
+    pub drain_window: Duration,
+}
+
+impl TailConfig {
+    /// Polling cadence only, with every timeout zero.
+    const fn polling(poll_interval: Duration, poll_interval_fast: Duration) -> Self {
+        Self {
+            poll_interval,
+            poll_interval_fast,
+            startup_timeout: Duration::ZERO,
+            startup_grace: Duration::ZERO,
+            dead_process_timeout: Duration::ZERO,
+            idle_timeout: Duration::ZERO,
+            output_file_grace: Duration::ZERO,
+            connect_grace: Duration::ZERO,
+            drain_window: Duration::ZERO,
+        }
+    }
+
+    /// Tail settings for a backend; tune a backend here rather than at the tail call site.
+    pub fn for_backend(backend: &Backend) -> Self {
+        match backend {
+            Backend::Claude => Self {
+                startup_timeout: Duration::from_secs(120),
+                startup_grace: Duration::from_secs(5),
+                dead_process_timeout: Duration::from_secs(2),
+                ..Self::polling(POLL_INTERVAL, POLL_INTERVAL_FAST)
+            },
+            // Long tool runs can keep a Codex turn quiet for minutes
+            Backend::Codex => Self {
+                idle_timeout: Duration::from_secs(300),
+                output_file_grace: Duration::from_secs(3),
+                ..Self::polling(Duration::from_millis(100), POLL_INTERVAL_FAST)
+            },
+            Backend::Opencode => Self {
+                connect_grace: Duration::from_secs(3),
+                drain_window: Duration::from_millis(750),
+                ..Self::polling(Duration::from_millis(500), Duration::from_millis(100))
+            },
+        }
+    }
+}
+
 /// Tailer for reading new lines from an NDJSON file.
 ///
 /// Maintains position in the file and returns only new complete lines
@@ -32,7 +101,6 @@ impl NdjsonTailer {
     ///
     /// This is used when starting to tail a file that's being written to,
     /// where we only want new content.
-    #[allow(dead_code)] // Used in tests
     pub fn new_at_end(path: &Path) -> Result<Self, String> {
         let file = File::open(path).map_err(|e| format!("Failed to open file for tailing: {e}"))?;
 
@@ -325,4 +393,24 @@ mod tests {
         assert_eq!(POLL_INTERVAL_FAST, Duration::from_millis(5));
         assert!(POLL_INTERVAL_FAST < POLL_INTERVAL);
     }
+
+    #[test]
+    fn test_backend_tail_configs() {
+        let claude = TailConfig::for_backend(&Backend::Claude);
+        assert_eq!(claude.poll_interval, POLL_INTERVAL);
+        assert_eq!(claude.startup_timeout, Duration::from_secs(120));
+        assert!(claude.startup_grace < claude.startup_timeout);
+        assert_eq!(claude.idle_timeout, Duration::ZERO);
+
+        let codex = TailConfig::for_backend(&Backend::Codex);
+        assert_eq!(codex.idle_timeout, Duration::from_secs(300));
+        assert!(codex.output_file_grace > Duration::ZERO);
+        assert_eq!(codex.startup_timeout, Duration::ZERO);
+
+        let opencode = TailConfig::for_backend(&Backend::Opencode);
+        assert!(opencode.poll_interval_fast < opencode.poll_interval);
+        assert!(opencode.connect_grace > Duration::ZERO);
+        assert!(opencode.drain_window < claude.dead_process_timeout);
+        assert_eq!(opencode.dead_process_timeout, Duration::ZERO);
+    }
 }
@@ -183,6 +183,9 @@ pub struct ToolCall {
     /// Parent tool use ID for sub-agent tool calls (for parallel task attribution)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub parent_tool_use_id: Option<String>,
+    /// File holding the untruncated output when `output` was capped (see `read_tool_output`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full_output_path: Option<String>,
 }
 
 /// A permission denial when a tool requires approval
@@ -218,14 +221,32 @@ pub struct DeniedMessageContext {
     pub thinking_level: String,
 }
 
-/// A content block in a message - text, tool use, or thinking
+/// One entry of an agent-maintained todo list
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TodoItem {
+    pub text: String,
+    /// "pending" or "completed"
+    pub status: String,
+}
+
+/// A content block in a message - text, tool use, thinking, a tool failure, or a todo list
 /// Used to preserve the order of content in Claude's response
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ContentBlock {
     Text { text: String },
     ToolUse { tool_call_id: String },
     Thinking { thinking: String },
+    /// The referenced tool completed with a failure status
+    Error {
+        tool_call_id: String,
+        message: String,
+    },
+    /// Latest state of the todo list with this id (Codex `todo_list` items)
+    Todo {
+        id: String,
+        items: Vec<TodoItem>,
+    },
 }
 
 /// A single chat message
@@ -268,6 +289,9 @@ pub struct ChatMessage {
     /// Token usage for this message (assistant messages only)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub usage: Option<UsageData>,
+    /// Files the turn edited, de-duplicated (Codex assistant messages only)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changed_files: Vec<String>,
 }
 
 impl Default for ChatMessage {
@@ -288,6 +312,7 @@ impl Default for ChatMessage {
             effort_level: None,
             recovered: false,
             usage: None,
+            changed_files: vec![],
         }
     }
 }
@@ -375,9 +400,15 @@ pub struct Session {
     /// Codex CLI thread ID for resuming conversations
     #[serde(default)]
     pub codex_thread_id: Option<String>,
+    /// Working directory the Codex thread was started in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub codex_thread_cwd: Option<String>,
     /// OpenCode session ID for resuming conversations
     #[serde(default)]
     pub opencode_session_id: Option<String>,
+    /// OpenCode server URL the OpenCode session was created on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server_url: Option<String>,
     /// Selected model for this session
     #[serde(default)]
     pub selected_model: Option<String>,
@@ -495,7 +526,9 @@ impl Session {
             backend,
             claude_session_id: None,
             codex_thread_id: None,
+            codex_thread_cwd: None,
             opencode_session_id: None,
+            server_url: None,
             selected_model: None,
             selected_thinking_level: None,
             selected_provider: None,
@@ -676,7 +709,9 @@ impl SessionMetadata {
             backend: self.backend.clone(),
             claude_session_id: self.claude_session_id.clone(),
             codex_thread_id: self.codex_thread_id.clone(),
+            codex_thread_cwd: self.codex_thread_cwd.clone(),
             opencode_session_id: self.opencode_session_id.clone(),
+            server_url: self.server_url.clone(),
             selected_model: self.selected_model.clone(),
             selected_thinking_level: self.selected_thinking_level.clone(),
             selected_provider: self.selected_provider.clone(),
@@ -714,7 +749,9 @@ impl SessionMetadata {
         self.backend = session.backend.clone();
         self.claude_session_id = session.claude_session_id.clone();
         self.codex_thread_id = session.codex_thread_id.clone();
+        self.codex_thread_cwd = session.codex_thread_cwd.clone();
         self.opencode_session_id = session.opencode_session_id.clone();
+        self.server_url = session.server_url.clone();
         self.selected_model = session.selected_model.clone();
         self.selected_thinking_level = session.selected_thinking_level.clone();
         self.selected_provider = session.selected_provider.clone();
@@ -951,6 +988,12 @@ pub struct RunEntry {
     /// Token usage for this run (captured from Claude CLI result)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub usage: Option<UsageData>,
+    /// Resolved path of the CLI binary that served this run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cli_path: Option<String>,
+    /// `--version` output of that binary
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cli_version: Option<String>,
 }
 
 /// Session metadata - single source of truth for session data and run history
@@ -978,9 +1021,15 @@ pub struct SessionMetadata {
     /// Codex CLI thread ID for resuming conversations
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub codex_thread_id: Option<String>,
+    /// Working directory the Codex thread was started in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub codex_thread_cwd: Option<String>,
     /// OpenCode session ID for resuming conversations
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub opencode_session_id: Option<String>,
+    /// OpenCode server URL the OpenCode session was created on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server_url: Option<String>,
     /// Selected model for this session
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub selected_model: Option<String>,
@@ -1093,6 +1142,12 @@ pub struct RunLogFileInfo {
     /// Token usage for this run (if completed)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub usage: Option<UsageData>,
+    /// CLI binary that served this run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cli_path: Option<String>,
+    /// Version reported by that binary
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cli_version: Option<String>,
 }
 
 /// Debug information about a session's storage
@@ -1132,7 +1187,9 @@ impl SessionMetadata {
             backend: Backend::default(),
             claude_session_id: None,
             codex_thread_id: None,
+            codex_thread_cwd: None,
             opencode_session_id: None,
+            server_url: None,
             selected_model: None,
             selected_thinking_level: None,
             selected_provider: None,
@@ -1387,6 +1444,34 @@ mod tests {
         assert!(json.contains("\"thinking\":\"Let me analyze...\""));
     }
 
+    #[test]
+    fn test_content_block_error_serialization() {
+        let block = ContentBlock::Error {
+            tool_call_id: "call-123".to_string(),
+            message: "Command exited with code 1".to_string(),
+        };
+        let json = serde_json::to_string(&block).unwrap();
+        assert!(json.contains("\"type\":\"error\""));
+        assert!(json.contains("\"tool_call_id\":\"call-123\""));
+        assert!(json.contains("\"message\":\"Command exited with code 1\""));
+    }
+
+    #[test]
+    fn test_content_block_todo_round_trip() {
+        let block = ContentBlock::Todo {
+            id: "todo-1".to_string(),
+            items: vec![TodoItem {
+                text: "Run tests".to_string(),
+                status: "completed".to_string(),
+            }],
+        };
+        let json = serde_json::to_string(&block).unwrap();
+        assert!(json.contains("\"type\":\"todo\""));
+        assert!(json.contains("\"status\":\"completed\""));
+        let parsed: ContentBlock = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, block);
+    }
+
     // ========================================================================
     // ChatMessage tests
     // ========================================================================
@@ -1426,6 +1511,7 @@ mod tests {
             input: serde_json::json!({"file_path": "/test.txt"}),
             output: Some("file contents".to_string()),
             parent_tool_use_id: None,
+            full_output_path: None,
         };
 
         let json = serde_json::to_string(&tool_call).unwrap();
@@ -1443,6 +1529,7 @@ mod tests {
             input: serde_json::json!({}),
             output: None,
             parent_tool_use_id: Some("call-123".to_string()),
+            full_output_path: None,
         };
 
         let json = serde_json::to_string(&tool_call).unwrap();
@@ -1518,6 +1605,8 @@ mod tests {
             claude_session_id: None,
             pid: Some(12345),
             usage: None,
+            cli_path: None,
+            cli_version: None,
         });
 
         assert!(metadata.find_run("run-1").is_some());
@@ -1554,6 +1643,8 @@ mod tests {
             claude_session_id: None,
             pid: None,
             usage: None,
+            cli_path: None,
+            cli_version: None,
         });
 
         assert!(metadata.latest_claude_session_id().is_none());
@@ -1576,6 +1667,8 @@ mod tests {
             claude_session_id: Some("claude-sess-abc".to_string()),
             pid: None,
             usage: None,
+            cli_path: None,
+            cli_version: None,
         });
 
         assert_eq!(metadata.latest_claude_session_id(), Some("claude-sess-abc"));
@@ -115,6 +115,7 @@ pub async fn check_claude_cli_installed(app: AppHandle) -> Result<ClaudeCliStatu
             if output.status.success() {
                 let version_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
                 log::trace!("Claude CLI raw version output: {}", version_str);
+                crate::chat::run_log::remember_cli_version(&binary_path, &version_str);
                 // claude --version returns just the version number like "1.0.28"
                 // but handle any prefix like "v1.0.28" or "Claude CLI 1.0.28"
                 let version = extract_version_number(&version_str);
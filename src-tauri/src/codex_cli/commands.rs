@@ -709,6 +709,7 @@ pub async fn check_codex_cli_installed(app: AppHandle) -> Result<CodexCliStatus,
         Ok(output) if output.status.success() => {
             let version_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
             log::debug!("check_codex_cli_installed: raw --version output={:?}", version_str);
+            crate::chat::run_log::remember_cli_version(&binary_path, &version_str);
             if version_str.is_empty() {
                 None
             } else {
@@ -0,0 +1,155 @@
+//! Toolchain diagnostics
+//!
+//! Runs a sequence of checks across the CLI binaries and the OpenCode server so
+//! users can tell whether a failure is the binary, the server, auth, or the model.
+
+use serde::Serialize;
+use tauri::AppHandle;
+
+/// Prompt used for the round-trip check. Kept trivial so it's cheap and fast.
+const ROUND_TRIP_PROMPT: &str = "Reply with the single word: hi";
+
+/// Outcome of a single diagnostic step
+#[derive(Debug, Clone, Serialize)]
+pub struct DiagnosticStep {
+    pub name: String,
+    pub passed: bool,
+    pub message: String,
+}
+
+/// Full toolchain diagnostics report
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolchainReport {
+    pub steps: Vec<DiagnosticStep>,
+    pub all_passed: bool,
+}
+
+fn step(name: &str, passed: bool, message: impl Into<String>) -> DiagnosticStep {
+    DiagnosticStep {
+        name: name.to_string(),
+        passed,
+        message: message.into(),
+    }
+}
+
+fn cli_step(
+    name: &str,
+    installed: bool,
+    version: Option<String>,
+    path: Option<String>,
+) -> DiagnosticStep {
+    if !installed {
+        return step(
+            name,
+            false,
+            "Not installed. Install it in Settings > General.",
+        );
+    }
+    let version = version.unwrap_or_else(|| "unknown version".to_string());
+    let path = path.unwrap_or_default();
+    step(name, true, format!("{version} ({path})"))
+}
+
+/// Run every toolchain check and return a structured pass/fail report.
+///
+/// Checks run in order; the prompt round-trip is skipped when the OpenCode
+/// server could not be started, since it would only repeat that failure.
+#[tauri::command]
+pub async fn diagnose_toolchain(app: AppHandle) -> Result<ToolchainReport, String> {
+    log::trace!("Running toolchain diagnostics");
+    let mut steps = Vec::new();
+
+    steps.push(
+        match crate::claude_cli::check_claude_cli_installed(app.clone()).await {
+            Ok(s) => cli_step("Claude CLI", s.installed, s.version, s.path),
+            Err(e) => step("Claude CLI", false, e),
+        },
+    );
+    steps.push(
+        match crate::codex_cli::check_codex_cli_installed(app.clone()).await {
+            Ok(s) => cli_step("Codex CLI", s.installed, s.version, s.path),
+            Err(e) => step("Codex CLI", false, e),
+        },
+    );
+    steps.push(
+        match crate::opencode_cli::check_opencode_cli_installed(app.clone()).await {
+            Ok(s) => cli_step("OpenCode CLI", s.installed, s.version, s.path),
+            Err(e) => step("OpenCode CLI", false, e),
+        },
+    );
+
+    // reqwest::blocking panics inside the Tokio runtime, so server work runs on
+    // a blocking thread.
+    let server_app = app.clone();
+    let server_result = tokio::task::spawn_blocking(move || {
+        let key = crate::opencode_server::ServerKey::Shared;
+        let url = crate::opencode_server::acquire(&server_app, &key)?;
+        crate::opencode_server::release(&key);
+        Ok::<String, String>(url)
+    })
+    .await
+    .map_err(|e| format!("Diagnostics task failed: {e}"))?;
+    let server_ok = server_result.is_ok();
+    steps.push(match server_result {
+        Ok(url) => step("OpenCode server", true, format!("Healthy at {url}")),
+        Err(e) => step("OpenCode server", false, e),
+    });
+
+    if server_ok {
+        let model = crate::load_preferences_sync(&app)
+            .map(|p| p.selected_opencode_model)
+            .unwrap_or_default();
+        let prompt_app = app.clone();
+        let prompt_model = model.clone();
+        let prompt_result = tokio::task::spawn_blocking(move || {
+            crate::chat::opencode::execute_one_shot_opencode(
+                &prompt_app,
+                ROUND_TRIP_PROMPT,
+                &prompt_model,
+                None,
+                None,
+                None,
+            )
+        })
+        .await
+        .map_err(|e| format!("Diagnostics task failed: {e}"))?;
+        steps.push(match prompt_result {
+            Ok(text) if !text.trim().is_empty() => step(
+                "Prompt round-trip",
+                true,
+                format!("{model} replied: {}", text.trim()),
+            ),
+            Ok(_) => step(
+                "Prompt round-trip",
+                false,
+                format!("{model} returned an empty response"),
+            ),
+            Err(e) => step("Prompt round-trip", false, e),
+        });
+    } else {
+        steps.push(step(
+            "Prompt round-trip",
+            false,
+            "Skipped: OpenCode server is not available",
+        ));
+    }
+
+    steps.push(
+        match crate::opencode_cli::list_opencode_models(app.clone()).await {
+            Ok(models) if !models.is_empty() => step(
+                "Model list",
+                true,
+                format!("{} models available", models.len()),
+            ),
+            Ok(_) => step(
+                "Model list",
+                false,
+                "No models available. Authenticate a provider first.",
+            ),
+            Err(e) => step("Model list", false, e),
+        },
+    );
+
+    let all_passed = steps.iter().all(|s| s.passed);
+    Ok(ToolchainReport { steps, all_passed })
+}
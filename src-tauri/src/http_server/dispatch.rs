@@ -878,6 +878,29 @@ pub async fn dispatch_command(
             crate::chat::cancel_chat_message(app.clone(), session_id, worktree_id).await?;
             Ok(Value::Null)
         }
+        "preview_codex_command" => {
+            let session_id: String = field(&args, "sessionId", "session_id")?;
+            let worktree_id: String = field(&args, "worktreeId", "worktree_id")?;
+            let worktree_path: String = field(&args, "worktreePath", "worktree_path")?;
+            let message: String = from_field(&args, "message")?;
+            let model: Option<String> = from_field_opt(&args, "model")?;
+            let execution_mode: Option<String> =
+                field_opt(&args, "executionMode", "execution_mode")?;
+            let reasoning_effort: Option<String> =
+                field_opt(&args, "reasoningEffort", "reasoning_effort")?;
+            let result = crate::chat::preview_codex_command(
+                app.clone(),
+                session_id,
+                worktree_id,
+                worktree_path,
+                message,
+                model,
+                execution_mode,
+                reasoning_effort,
+            )
+            .await?;
+            to_value(result)
+        }
         "clear_session_history" => {
             let worktree_id: String = field(&args, "worktreeId", "worktree_id")?;
             let worktree_path: String = field(&args, "worktreePath", "worktree_path")?;
@@ -985,6 +1008,11 @@ pub async fn dispatch_command(
             let result = crate::chat::read_context_file(app.clone(), path).await?;
             to_value(result)
         }
+        "read_tool_output" => {
+            let path: String = from_field(&args, "path")?;
+            let result = crate::chat::read_tool_output(app.clone(), path).await?;
+            to_value(result)
+        }
         "delete_context_file" => {
             let path: String = from_field(&args, "path")?;
             crate::chat::delete_context_file(app.clone(), path).await?;
@@ -1792,15 +1820,57 @@ pub async fn dispatch_command(
             to_value(result)
         }
         "start_opencode_server" => {
-            let result = crate::opencode_server::start_opencode_server(app.clone()).await?;
+            let worktree_id: Option<String> = field_opt(&args, "worktreeId", "worktree_id")?;
+            let result =
+                crate::opencode_server::start_opencode_server(app.clone(), worktree_id).await?;
             to_value(result)
         }
         "stop_opencode_server" => {
             crate::opencode_server::stop_opencode_server().await?;
             Ok(Value::Null)
         }
+        "tail_opencode_log" => {
+            let worktree_id: Option<String> = field_opt(&args, "worktreeId", "worktree_id")?;
+            crate::opencode_server::tail_opencode_log(app.clone(), worktree_id).await?;
+            Ok(Value::Null)
+        }
+        "untail_opencode_log" => {
+            crate::opencode_server::untail_opencode_log().await?;
+            Ok(Value::Null)
+        }
+        "get_opencode_server_auth" => {
+            let result = crate::opencode_server::get_opencode_server_auth(app.clone()).await?;
+            to_value(result)
+        }
+        "set_opencode_server_auth" => {
+            let name: Option<String> = from_field_opt(&args, "name")?;
+            let value: Option<String> = from_field_opt(&args, "value")?;
+            crate::opencode_server::set_opencode_server_auth(app.clone(), name, value).await?;
+            Ok(Value::Null)
+        }
+        "begin_opencode_server_drain" => {
+            crate::opencode_server::begin_opencode_server_drain().await?;
+            Ok(Value::Null)
+        }
+        "end_opencode_server_drain" => {
+            crate::opencode_server::end_opencode_server_drain().await?;
+            Ok(Value::Null)
+        }
         "get_opencode_server_status" => {
-            let result = crate::opencode_server::get_opencode_server_status().await?;
+            let worktree_id: Option<String> = field_opt(&args, "worktreeId", "worktree_id")?;
+            let result =
+                crate::opencode_server::get_opencode_server_status(app.clone(), worktree_id)
+                    .await?;
+            to_value(result)
+        }
+        "probe_opencode_port" => {
+            let worktree_id: Option<String> = field_opt(&args, "worktreeId", "worktree_id")?;
+            let result =
+                crate::opencode_server::probe_opencode_port(app.clone(), worktree_id).await?;
+            to_value(result)
+        }
+        "diagnose_toolchain" => {
+            let result = crate::diagnostics::diagnose_toolchain(app.clone()).await?;
             to_value(result)
         }
 
@@ -12,6 +12,7 @@ mod background_tasks;
 mod chat;
 mod claude_cli;
 mod codex_cli;
+mod diagnostics;
 mod gh_cli;
 pub mod http_server;
 mod opencode_cli;
@@ -224,6 +225,30 @@ pub struct AppPreferences {
     pub opencode_cli_source: String, // OpenCode CLI source: "jean" (managed) or "path" (system PATH)
     #[serde(default = "default_cli_source")]
     pub gh_cli_source: String, // GitHub CLI source: "jean" (managed) or "path" (system PATH)
+    #[serde(default)]
+    pub opencode_external_server_url: Option<String>, // External OpenCode server to use instead of spawning one (None = Jean-managed)
+    #[serde(default = "default_codex_tool_output_max_kb")]
+    pub codex_tool_output_max_kb: u32, // Truncate Codex command outputs above this size in KB (full output saved to disk)
+    #[serde(default = "default_codex_web_search_mode")]
+    pub codex_web_search_mode: String, // Codex web search when web tools are allowed: "live" or "cached"
+    #[serde(default)]
+    pub codex_reasoning_summary: Option<String>, // Codex reasoning summary: "auto", "concise", "detailed" or "none" (None = Codex default)
+    #[serde(default = "default_opencode_server_spawn_retries")]
+    pub opencode_server_spawn_retries: u32, // Extra OpenCode server spawn attempts when the health check times out
+    #[serde(default = "default_codex_stall_warning_secs")]
+    pub codex_stall_warning_secs: u32, // Warn when Codex sends nothing for this many seconds (0 = never)
+    #[serde(default)]
+    pub codex_model_provider: Option<String>, // Codex model_provider override, e.g. an Azure or gateway provider from Codex config (None = OpenAI)
+    #[serde(default)]
+    pub opencode_server_memory_limit_mb: Option<u32>, // Unix data-segment (heap) cap for the managed OpenCode server in MB (None = unlimited)
+    #[serde(default)]
+    pub opencode_serve_subcommand: Option<String>, // Subcommand that starts the OpenCode server (None = "serve")
+    #[serde(default)]
+    pub opencode_extra_serve_args: Vec<String>, // Extra args appended after --hostname/--port when starting the OpenCode server
+    #[serde(default = "default_max_concurrent_tailers")]
+    pub max_concurrent_tailers: u32, // Most sessions that may stream output at once; new ones are rejected beyond this (0 = unlimited)
+    #[serde(default)]
+    pub opencode_server_per_project: bool, // Run a separate managed OpenCode server per project instead of one shared server
 }
 
 fn default_true() -> Option<bool> {
@@ -392,6 +417,26 @@ fn default_cli_source() -> String {
     "jean".to_string()
 }
 
+fn default_codex_tool_output_max_kb() -> u32 {
+    256 // Codex command outputs above 256 KB are truncated for the UI
+}
+
+fn default_codex_web_search_mode() -> String {
+    "cached".to_string() // Matches Codex's own web_search default
+}
+
+fn default_opencode_server_spawn_retries() -> u32 {
+    2 // Retry a slow or port-raced OpenCode server start twice
+}
+
+fn default_codex_stall_warning_secs() -> u32 {
+    30 // Tell the user Codex is taking a while after 30s of silence
+}
+
+fn default_max_concurrent_tailers() -> u32 {
+    32 // Each tailer holds a thread and file handle; plenty for parallel sessions
+}
+
 fn default_codex_model() -> String {
     "gpt-5.4".to_string()
 }
@@ -1146,6 +1191,18 @@ impl Default for AppPreferences {
             codex_cli_source: default_cli_source(),
             opencode_cli_source: default_cli_source(),
             gh_cli_source: default_cli_source(),
+            opencode_external_server_url: None,
+            codex_tool_output_max_kb: default_codex_tool_output_max_kb(),
+            codex_web_search_mode: default_codex_web_search_mode(),
+            codex_reasoning_summary: None,
+            opencode_server_spawn_retries: default_opencode_server_spawn_retries(),
+            codex_stall_warning_secs: default_codex_stall_warning_secs(),
+            codex_model_provider: None,
+            opencode_server_memory_limit_mb: None,
+            opencode_serve_subcommand: None,
+            opencode_extra_serve_args: Vec::new(),
+            max_concurrent_tailers: default_max_concurrent_tailers(),
+            opencode_server_per_project: false,
         }
     }
 }
@@ -2364,6 +2421,9 @@ pub fn run() {
                 .ok();
             });
 
+            // Tear down managed servers on SIGTERM/SIGINT (console close on Windows).
+            tauri::async_runtime::spawn(opencode_server::shutdown_on_termination_signal());
+
             log::info!("Startup: orphaned server cleanup spawned at {:?}", setup_start.elapsed());
 
             // Allow image access from all known project/worktree directories.
@@ -2779,6 +2839,7 @@ pub fn run() {
             chat::set_session_thinking_level,
             chat::set_session_provider,
             chat::cancel_chat_message,
+            chat::preview_codex_command,
             chat::has_running_sessions,
             chat::save_cancelled_message,
             chat::mark_plan_approved,
@@ -2808,6 +2869,7 @@ pub fn run() {
             chat::list_saved_contexts,
             chat::save_context_file,
             chat::read_context_file,
+            chat::read_tool_output,
             chat::delete_context_file,
             chat::rename_saved_context,
             chat::generate_context_from_session,
@@ -2871,6 +2933,14 @@ pub fn run() {
             opencode_server::start_opencode_server,
             opencode_server::stop_opencode_server,
             opencode_server::get_opencode_server_status,
+            opencode_server::probe_opencode_port,
+            opencode_server::begin_opencode_server_drain,
+            opencode_server::end_opencode_server_drain,
+            opencode_server::tail_opencode_log,
+            opencode_server::untail_opencode_log,
+            opencode_server::get_opencode_server_auth,
+            opencode_server::set_opencode_server_auth,
+            diagnostics::diagnose_toolchain,
         ])
         .build(tauri::generate_context!())
         .expect("error building tauri application")
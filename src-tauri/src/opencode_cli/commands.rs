@@ -138,6 +138,7 @@ pub async fn check_opencode_cli_installed(app: AppHandle) -> Result<OpenCodeCliS
     let version = match silent_command(&binary_path).arg("--version").output() {
         Ok(output) if output.status.success() => {
             let version_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            crate::chat::run_log::remember_cli_version(&binary_path, &version_str);
             let cleaned = version_str
                 .split_whitespace()
                 .last()
@@ -0,0 +1,189 @@
+//! Optional auth header for OpenCode servers behind an auth proxy
+//!
+//! The header value is a secret, so it is kept out of preferences: macOS stores
+//! it in the keychain, other platforms in an owner-only file in app data.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+#[cfg(target_os = "macos")]
+const KEYCHAIN_SERVICE: &str = "Jean OpenCode Server Auth";
+#[cfg(target_os = "macos")]
+const KEYCHAIN_ACCOUNT: &str = "jean";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AuthHeader {
+    name: String,
+    value: String,
+}
+
+/// Auth header as shown to the frontend, with the value redacted
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenCodeServerAuthInfo {
+    pub name: String,
+    pub redacted_value: String,
+}
+
+/// Cached header; the outer `None` means storage hasn't been read yet.
+static AUTH_HEADER: Lazy<Mutex<Option<Option<AuthHeader>>>> = Lazy::new(|| Mutex::new(None));
+
+#[cfg(not(target_os = "macos"))]
+fn auth_file_path(app: &AppHandle) -> Option<std::path::PathBuf> {
+    use tauri::Manager;
+    app.path()
+        .app_data_dir()
+        .ok()
+        .map(|d| d.join("opencode-server-auth.json"))
+}
+
+/// Mask a header value for logs and UI, keeping a scheme like `Bearer`.
+fn redact(value: &str) -> String {
+    match value.split_once(' ') {
+        Some((scheme, _)) => format!("{scheme} ****"),
+        None => "****".to_string(),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn load_stored(_app: &AppHandle) -> Option<AuthHeader> {
+    let payload =
+        security_framework::passwords::get_generic_password(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT)
+            .ok()?;
+    serde_json::from_slice(&payload).ok()
+}
+
+#[cfg(not(target_os = "macos"))]
+fn load_stored(app: &AppHandle) -> Option<AuthHeader> {
+    let content = std::fs::read_to_string(auth_file_path(app)?).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Uses the Security framework directly; the `security` CLI would need the
+/// secret on its command line, where any local user can read it with `ps`.
+#[cfg(target_os = "macos")]
+fn store(_app: &AppHandle, header: Option<&AuthHeader>) -> Result<(), String> {
+    use security_framework::passwords;
+
+    let Some(header) = header else {
+        // Missing entry is fine when clearing
+        let _ = passwords::delete_generic_password(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT);
+        return Ok(());
+    };
+    let payload = serde_json::to_vec(header)
+        .map_err(|e| format!("Failed to serialize OpenCode auth header: {e}"))?;
+    passwords::set_generic_password(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT, &payload)
+        .map_err(|e| format!("Failed to update OpenCode auth keychain entry: {e}"))
+}
+
+#[cfg(not(target_os = "macos"))]
+fn store(app: &AppHandle, header: Option<&AuthHeader>) -> Result<(), String> {
+    use std::io::Write;
+
+    let path = auth_file_path(app).ok_or("Failed to resolve app data directory")?;
+    let Some(header) = header else {
+        let _ = std::fs::remove_file(&path);
+        return Ok(());
+    };
+    let content = serde_json::to_string(header)
+        .map_err(|e| format!("Failed to serialize OpenCode auth header: {e}"))?;
+
+    // Owner-only from creation, so the secret is never readable by others
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(&path)
+        .map_err(|e| format!("Failed to write {}: {e}", path.display()))?;
+    // `mode` only applies to new files; tighten one left by an older version
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))
+            .map_err(|e| format!("Failed to restrict {}: {e}", path.display()))?;
+    }
+    file.write_all(content.as_bytes())
+        .map_err(|e| format!("Failed to write {}: {e}", path.display()))
+}
+
+fn current(app: Option<&AppHandle>) -> Option<AuthHeader> {
+    let mut cache = AUTH_HEADER.lock().unwrap_or_else(|e| e.into_inner());
+    if cache.is_none() {
+        // Not read yet; without an app handle there is nowhere to read from
+        *cache = Some(app.and_then(load_stored));
+    }
+    cache.clone().flatten()
+}
+
+/// Default headers for every request Jean sends to the OpenCode server.
+/// Empty when no auth header is configured.
+pub fn auth_headers() -> reqwest::header::HeaderMap {
+    let mut headers = reqwest::header::HeaderMap::new();
+    let Some(header) = current(super::APP_HANDLE.get()) else {
+        return headers;
+    };
+    let name = reqwest::header::HeaderName::from_bytes(header.name.as_bytes());
+    let value = reqwest::header::HeaderValue::from_str(&header.value);
+    match (name, value) {
+        (Ok(name), Ok(mut value)) => {
+            value.set_sensitive(true);
+            headers.insert(name, value);
+        }
+        _ => log::warn!(
+            "Ignoring invalid OpenCode auth header {}: {}",
+            header.name,
+            redact(&header.value)
+        ),
+    }
+    headers
+}
+
+#[tauri::command]
+pub async fn get_opencode_server_auth(
+    app: AppHandle,
+) -> Result<Option<OpenCodeServerAuthInfo>, String> {
+    Ok(current(Some(&app)).map(|h| OpenCodeServerAuthInfo {
+        redacted_value: redact(&h.value),
+        name: h.name,
+    }))
+}
+
+/// Set or clear (blank name or value) the auth header sent to the OpenCode server.
+#[tauri::command]
+pub async fn set_opencode_server_auth(
+    app: AppHandle,
+    name: Option<String>,
+    value: Option<String>,
+) -> Result<(), String> {
+    let name = name.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    let value = value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty());
+    let header = match (name, value) {
+        (Some(name), Some(value)) => {
+            reqwest::header::HeaderName::from_bytes(name.as_bytes())
+                .map_err(|e| format!("Invalid header name: {e}"))?;
+            reqwest::header::HeaderValue::from_str(&value)
+                .map_err(|e| format!("Invalid header value: {e}"))?;
+            Some(AuthHeader { name, value })
+        }
+        _ => None,
+    };
+
+    store(&app, header.as_ref())?;
+    match &header {
+        Some(h) => log::info!(
+            "OpenCode server auth header set: {}: {}",
+            h.name,
+            redact(&h.value)
+        ),
+        None => log::info!("OpenCode server auth header cleared"),
+    }
+    *AUTH_HEADER.lock().unwrap_or_else(|e| e.into_inner()) = Some(header);
+    Ok(())
+}
@@ -1,58 +1,488 @@
+mod auth;
+
+pub use auth::*;
+
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
-use std::process::{Child, Stdio};
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Mutex;
-use std::time::Duration;
+use std::process::{Child, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Manager};
 
+use crate::http_server::EmitExt;
 use crate::opencode_cli::resolve_cli_binary;
 use crate::platform::silent_command;
 
 const DEFAULT_PORT: u16 = 4096;
 const DEFAULT_HOSTNAME: &str = "127.0.0.1";
+/// How far past `DEFAULT_PORT` to look for a free port for per-project servers.
+const PROJECT_PORT_RANGE: u16 = 100;
 
-/// Number of active consumers (prompts) using the managed server.
-/// Server is shut down only when this drops to 0.
-static USAGE_COUNT: AtomicUsize = AtomicUsize::new(0);
+/// Number of active consumers (prompts) per managed server.
+/// A server is shut down only when its count drops to 0.
+static USAGE_COUNTS: Lazy<Mutex<HashMap<ServerKey, usize>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
 
 /// Cached AppHandle so stop/release paths can access app data dir without param changes.
 static APP_HANDLE: once_cell::sync::OnceCell<AppHandle> = once_cell::sync::OnceCell::new();
 
+/// Whether the frontend currently wants `opencode-server:log` events.
+static LOG_SUBSCRIBED: AtomicBool = AtomicBool::new(false);
+
+/// Guards against spawning more than one log tailer thread.
+static LOG_TAILER_RUNNING: AtomicBool = AtomicBool::new(false);
+
+/// Server whose log the tailer follows. A running tailer switches files when
+/// this changes, so only one log is streamed at a time.
+static LOG_TAIL_KEY: Lazy<Mutex<ServerKey>> = Lazy::new(|| Mutex::new(ServerKey::Shared));
+
+/// While set, new `acquire` calls are rejected and each managed server stops
+/// as soon as its last consumer releases it.
+static DRAINING: AtomicBool = AtomicBool::new(false);
+
+/// Identifies a managed server. Everything uses `Shared` unless per-project
+/// servers are enabled, in which case each project gets its own server and port.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ServerKey {
+    Shared,
+    Project(String),
+}
+
+impl ServerKey {
+    /// Key for work in `worktree_id`, honoring the per-project preference.
+    /// Falls back to the shared server when the worktree can't be resolved.
+    /// Reads preferences and projects from disk, so callers that already have
+    /// them loaded should use `resolve` instead.
+    pub fn for_worktree(app: &AppHandle, worktree_id: &str) -> Self {
+        let per_project = crate::load_preferences_sync(app)
+            .map(|p| p.opencode_server_per_project)
+            .unwrap_or(false);
+        if !per_project {
+            return Self::Shared;
+        }
+        let data = crate::projects::storage::load_projects_data(app).ok();
+        let project_id = data
+            .as_ref()
+            .and_then(|data| data.find_worktree(worktree_id))
+            .map(|w| w.project_id.as_str());
+        Self::resolve(per_project, project_id)
+    }
+
+    /// Key for a worktree in `project_id` given the per-project preference.
+    pub fn resolve(per_project: bool, project_id: Option<&str>) -> Self {
+        match project_id {
+            Some(id) if per_project => Self::Project(id.to_string()),
+            _ => Self::Shared,
+        }
+    }
+
+    /// Key for an optional worktree; `None` means the shared server.
+    fn for_request(app: &AppHandle, worktree_id: Option<&str>) -> Self {
+        worktree_id
+            .map(|id| Self::for_worktree(app, id))
+            .unwrap_or(Self::Shared)
+    }
+}
+
 #[derive(Debug)]
 struct OpenCodeServerProcess {
     child: Child,
     port: u16,
     hostname: String,
+    /// Data-segment cap applied at spawn (Unix only)
+    memory_limit_mb: Option<u32>,
 }
 
 #[derive(Debug, Clone, Serialize)]
 pub struct OpenCodeServerStatus {
+    /// Server answers health checks
     pub running: bool,
+    /// Server process exists. `process_alive && !running` means it is wedged.
+    pub process_alive: bool,
     pub url: Option<String>,
     pub port: Option<u16>,
     pub hostname: Option<String>,
     pub managed: bool,
+    /// New prompts are being rejected until the server stops
+    pub draining: bool,
+}
+
+/// Result of a TCP-only reachability check (see `probe_opencode_port`)
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenCodePortProbe {
+    pub hostname: String,
+    pub port: u16,
+    pub reachable: bool,
+}
+
+/// How the managed server is launched:
+/// `<cli> <subcommand> --hostname <host> --port <port> <extra_args...>`
+#[derive(Debug, Clone)]
+struct ServeCommand {
+    subcommand: String,
+    extra_args: Vec<String>,
+}
+
+impl ServeCommand {
+    /// Build from preferences, rejecting extra args that would move the server
+    /// off the host/port we health-check.
+    fn from_prefs(prefs: Option<&crate::AppPreferences>) -> Result<Self, String> {
+        let subcommand = prefs
+            .and_then(|p| p.opencode_serve_subcommand.as_deref())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .unwrap_or("serve")
+            .to_string();
+        let extra_args: Vec<String> = prefs
+            .map(|p| p.opencode_extra_serve_args.clone())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|a| !a.trim().is_empty())
+            .collect();
+
+        if let Some(arg) = extra_args.iter().find(|a| {
+            ["--port", "--hostname"]
+                .iter()
+                .any(|flag| *a == flag || a.starts_with(&format!("{flag}=")))
+        }) {
+            return Err(format!(
+                "OpenCode extra serve argument {arg} conflicts with the managed host/port. Remove it in Settings."
+            ));
+        }
+
+        Ok(Self {
+            subcommand,
+            extra_args,
+        })
+    }
+
+    fn args(&self, hostname: &str, port: u16) -> Vec<String> {
+        let mut args = vec![
+            self.subcommand.clone(),
+            "--hostname".to_string(),
+            hostname.to_string(),
+            "--port".to_string(),
+            port.to_string(),
+        ];
+        args.extend(self.extra_args.iter().cloned());
+        args
+    }
 }
 
-static OPENCODE_SERVER: Lazy<Mutex<Option<OpenCodeServerProcess>>> = Lazy::new(|| Mutex::new(None));
+/// One slot per server key. The outer lock is held only to look up a slot, so a
+/// slow cold start for one project doesn't block prompts on another.
+type ServerSlot = Arc<Mutex<Option<OpenCodeServerProcess>>>;
+
+static OPENCODE_SERVERS: Lazy<Mutex<HashMap<ServerKey, ServerSlot>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Port assigned to each project key while its server is in use. Dropped when
+/// the server stops idle (see `forget_idle_server`); the next start usually
+/// gets the same port back since allocation takes the lowest free one.
+static SERVER_PORTS: Lazy<Mutex<HashMap<ServerKey, u16>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// How long `probe_opencode_port` waits for a TCP connection.
+const PORT_PROBE_TIMEOUT: Duration = Duration::from_millis(300);
+
+/// How long a passed health check is trusted for a live managed server, so
+/// rapid successive prompts skip the HTTP round-trip.
+const HEALTH_CACHE_TTL: Duration = Duration::from_secs(2);
+
+/// Time of the last passed health check per server URL.
+static LAST_HEALTHY: Lazy<Mutex<HashMap<String, Instant>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Consecutive failed cold starts within `BREAKER_WINDOW` that trip the breaker.
+const BREAKER_THRESHOLD: u32 = 3;
+const BREAKER_WINDOW: Duration = Duration::from_secs(300);
+/// How long a tripped breaker fails fast before allowing another spawn.
+const BREAKER_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// Tracks failed cold starts for one server key so a broken CLI doesn't cost a
+/// full health timeout on every prompt.
+#[derive(Default)]
+struct SpawnBreaker {
+    failures: u32,
+    first_failure: Option<Instant>,
+    open_until: Option<Instant>,
+    last_error: String,
+}
+
+/// One breaker per server key, so a project whose server keeps failing doesn't
+/// block cold starts for the others.
+static SPAWN_BREAKERS: Lazy<Mutex<HashMap<ServerKey, SpawnBreaker>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Why a managed server running under a memory limit last exited abnormally,
+/// held until the next prompt on that key reports it to the user.
+static MEMORY_LIMIT_EXITS: Lazy<Mutex<HashMap<ServerKey, String>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Emitted as `opencode-server:breaker` when repeated start failures trip the breaker.
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenCodeServerBreakerEvent {
+    pub message: String,
+    pub retry_after_secs: u64,
+}
 
 fn server_url(hostname: &str, port: u16) -> String {
     format!("http://{hostname}:{port}")
 }
 
+fn server_slot(key: &ServerKey) -> Result<ServerSlot, String> {
+    let mut servers = OPENCODE_SERVERS
+        .lock()
+        .map_err(|e| format!("OpenCode server lock error: {e}"))?;
+    Ok(servers.entry(key.clone()).or_default().clone())
+}
+
+fn server_keys() -> Vec<ServerKey> {
+    OPENCODE_SERVERS
+        .lock()
+        .map(|servers| servers.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// Port for `key`'s server. The shared server always uses `DEFAULT_PORT`;
+/// project servers get the first free port above it not assigned to another key.
+fn server_port(key: &ServerKey) -> Result<u16, String> {
+    if *key == ServerKey::Shared {
+        return Ok(DEFAULT_PORT);
+    }
+    let mut ports = SERVER_PORTS
+        .lock()
+        .map_err(|e| format!("OpenCode port lock error: {e}"))?;
+    if let Some(port) = ports.get(key) {
+        return Ok(*port);
+    }
+    let port = next_project_port(&ports, |port| {
+        std::net::TcpListener::bind((DEFAULT_HOSTNAME, port)).is_ok()
+    })
+    .ok_or("No free port available for a per-project OpenCode server")?;
+    ports.insert(key.clone(), port);
+    Ok(port)
+}
+
+/// Lowest port in the project range that isn't assigned to another key and
+/// that `is_free` accepts.
+fn next_project_port(
+    assigned: &HashMap<ServerKey, u16>,
+    is_free: impl Fn(u16) -> bool,
+) -> Option<u16> {
+    (DEFAULT_PORT + 1..=DEFAULT_PORT + PROJECT_PORT_RANGE)
+        .find(|port| !assigned.values().any(|p| p == port) && is_free(*port))
+}
+
+/// Port already assigned to `key`, without reserving one. `None` means no
+/// server has been started for that project yet.
+fn assigned_port(key: &ServerKey) -> Option<u16> {
+    if *key == ServerKey::Shared {
+        return Some(DEFAULT_PORT);
+    }
+    SERVER_PORTS
+        .lock()
+        .ok()
+        .and_then(|ports| ports.get(key).copied())
+}
+
+fn usage_count(key: &ServerKey) -> usize {
+    USAGE_COUNTS
+        .lock()
+        .map(|counts| counts.get(key).copied().unwrap_or(0))
+        .unwrap_or(0)
+}
+
+fn total_usage() -> usize {
+    USAGE_COUNTS
+        .lock()
+        .map(|counts| counts.values().sum())
+        .unwrap_or(0)
+}
+
+/// Count a new user of `key`'s server unless a drain is in progress. The drain
+/// flag is read under the counts lock, so `begin_drain` either sees this user
+/// or this call sees the drain; a server can't be stopped under a new user.
+fn add_user(key: &ServerKey) -> Result<(), String> {
+    let mut counts = USAGE_COUNTS
+        .lock()
+        .map_err(|e| format!("OpenCode usage lock error: {e}"))?;
+    if DRAINING.load(Ordering::SeqCst) {
+        return Err(
+            "OpenCode server is draining for maintenance. Try again once it has stopped."
+                .to_string(),
+        );
+    }
+    *counts.entry(key.clone()).or_insert(0) += 1;
+    Ok(())
+}
+
+/// Adjust `key`'s usage count by one and return the previous count.
+fn update_usage(key: &ServerKey, increment: bool) -> usize {
+    let Ok(mut counts) = USAGE_COUNTS.lock() else {
+        return 0;
+    };
+    let count = counts.entry(key.clone()).or_insert(0);
+    let prev = *count;
+    *count = if increment {
+        prev + 1
+    } else {
+        prev.saturating_sub(1)
+    };
+    prev
+}
+
 fn is_healthy(url: &str) -> bool {
     let health_url = format!("{url}/global/health");
-    reqwest::blocking::Client::new()
+    let healthy = reqwest::blocking::Client::new()
         .get(health_url)
+        .headers(auth_headers())
         .timeout(Duration::from_millis(1200))
         .send()
         .map(|r| r.status().is_success())
+        .unwrap_or(false);
+    if let Ok(mut last) = LAST_HEALTHY.lock() {
+        if healthy {
+            last.insert(url.to_string(), Instant::now());
+        } else {
+            last.remove(url);
+        }
+    }
+    healthy
+}
+
+/// Whether `url` passed a health check within `HEALTH_CACHE_TTL`.
+fn recently_healthy(url: &str) -> bool {
+    LAST_HEALTHY
+        .lock()
+        .ok()
+        .and_then(|last| last.get(url).map(|at| at.elapsed() < HEALTH_CACHE_TTL))
         .unwrap_or(false)
 }
 
+fn invalidate_health_cache(url: &str) {
+    if let Ok(mut last) = LAST_HEALTHY.lock() {
+        last.remove(url);
+    }
+}
+
+/// Fail fast with the cached error while the breaker is open. Once the cooldown
+/// passes, one more spawn is allowed; a single further failure re-trips it.
+impl SpawnBreaker {
+    /// Fail fast while tripped. Once the cooldown passes, one spawn is let
+    /// through and a single further failure trips the breaker again.
+    fn check(&mut self, now: Instant) -> Result<(), String> {
+        match self.open_until {
+            Some(until) if now < until => Err(format!(
+                "OpenCode server repeatedly failed to start; retrying in {}s. Last error: {}",
+                until.saturating_duration_since(now).as_secs().max(1),
+                self.last_error
+            )),
+            Some(_) => {
+                self.open_until = None;
+                self.failures = BREAKER_THRESHOLD - 1;
+                self.first_failure = Some(now);
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Count a failed cold start. Returns a message when this failure trips
+    /// the breaker.
+    fn record_failure(&mut self, error: &str, now: Instant) -> Option<String> {
+        if self
+            .first_failure
+            .is_none_or(|first| now.duration_since(first) > BREAKER_WINDOW)
+        {
+            self.failures = 0;
+            self.first_failure = Some(now);
+        }
+        self.failures += 1;
+        self.last_error = error.to_string();
+
+        if self.failures < BREAKER_THRESHOLD {
+            return None;
+        }
+        self.open_until = Some(now + BREAKER_COOLDOWN);
+        Some(format!(
+            "OpenCode server repeatedly failed to start ({} attempts). Last error: {error}",
+            self.failures
+        ))
+    }
+}
+
+fn check_spawn_breaker(key: &ServerKey) -> Result<(), String> {
+    let mut breakers = SPAWN_BREAKERS
+        .lock()
+        .map_err(|e| format!("OpenCode breaker lock error: {e}"))?;
+    match breakers.get_mut(key) {
+        Some(breaker) => breaker.check(Instant::now()),
+        None => Ok(()),
+    }
+}
+
+/// Update `key`'s breaker for a spawn outcome: a success clears it, a failure
+/// counts towards tripping it. Returns a message when the breaker trips.
+fn record_spawn_outcome(
+    breakers: &mut HashMap<ServerKey, SpawnBreaker>,
+    key: &ServerKey,
+    result: &Result<String, String>,
+    now: Instant,
+) -> Option<String> {
+    match result {
+        Ok(_) => {
+            breakers.remove(key);
+            None
+        }
+        Err(error) => breakers
+            .entry(key.clone())
+            .or_default()
+            .record_failure(error, now),
+    }
+}
+
+fn record_spawn_result(app: &AppHandle, key: &ServerKey, result: &Result<String, String>) {
+    let Ok(mut breakers) = SPAWN_BREAKERS.lock() else {
+        return;
+    };
+    if let Some(message) = record_spawn_outcome(&mut breakers, key, result, Instant::now()) {
+        log::warn!("{message}");
+        let _ = app.emit_all(
+            "opencode-server:breaker",
+            &OpenCodeServerBreakerEvent {
+                message,
+                retry_after_secs: BREAKER_COOLDOWN.as_secs(),
+            },
+        );
+    }
+}
+
+/// User-configured OpenCode server to talk to instead of spawning our own.
+/// Returns `None` when unset or blank, meaning Jean manages the server.
+fn external_server_url(app: &AppHandle) -> Option<String> {
+    let prefs = crate::load_preferences_sync(app).ok()?;
+    prefs
+        .opencode_external_server_url
+        .map(|u| u.trim().trim_end_matches('/').to_string())
+        .filter(|u| !u.is_empty())
+}
+
+/// Split a base URL into (hostname, port) for status reporting.
+fn url_host_port(url: &str) -> (Option<String>, Option<u16>) {
+    match reqwest::Url::parse(url) {
+        Ok(parsed) => (
+            parsed.host_str().map(|h| h.to_string()),
+            parsed.port_or_known_default(),
+        ),
+        Err(_) => (None, None),
+    }
+}
+
 fn wait_until_healthy(url: &str, attempts: u32) -> bool {
     for _ in 0..attempts {
         if is_healthy(url) {
@@ -74,15 +504,43 @@ struct ServerPidRecord {
     port: u16,
 }
 
-fn pid_file_path() -> Option<PathBuf> {
+/// App data file for the server on `port`. The shared server keeps the
+/// unsuffixed name; per-project servers are suffixed with their port.
+fn server_file_path(port: u16, extension: &str) -> Option<PathBuf> {
+    let name = if port == DEFAULT_PORT {
+        format!("opencode-server.{extension}")
+    } else {
+        format!("opencode-server-{port}.{extension}")
+    };
     APP_HANDLE
         .get()
         .and_then(|app| app.path().app_data_dir().ok())
-        .map(|d| d.join("opencode-server.pid"))
+        .map(|d| d.join(name))
+}
+
+fn pid_file_path(port: u16) -> Option<PathBuf> {
+    server_file_path(port, "pid")
+}
+
+/// Managed server stdout/stderr, truncated on each spawn.
+fn log_file_path(port: u16) -> Option<PathBuf> {
+    server_file_path(port, "log")
+}
+
+/// Open the server log for a fresh spawn, returning stdout/stderr handles.
+/// Falls back to discarding output if the file can't be created.
+fn server_log_stdio(port: u16) -> (Stdio, Stdio) {
+    let file = log_file_path(port).and_then(|path| fs::File::create(path).ok());
+    match file.and_then(|f| f.try_clone().ok().map(|clone| (f, clone))) {
+        Some((out, err)) => (Stdio::from(out), Stdio::from(err)),
+        None => (Stdio::null(), Stdio::null()),
+    }
 }
 
 fn write_pid_file(server_pid: u32, port: u16) {
-    let Some(path) = pid_file_path() else { return };
+    let Some(path) = pid_file_path(port) else {
+        return;
+    };
     let record = ServerPidRecord {
         jean_pid: std::process::id(),
         server_pid,
@@ -93,24 +551,36 @@ fn write_pid_file(server_pid: u32, port: u16) {
     }
 }
 
-fn remove_pid_file() {
-    if let Some(path) = pid_file_path() {
+fn remove_pid_file(port: u16) {
+    if let Some(path) = pid_file_path(port) {
         let _ = fs::remove_file(path);
     }
 }
 
-/// Kill an orphaned OpenCode server left behind by a previous Jean crash.
+/// Kill orphaned OpenCode servers left behind by a previous Jean crash.
 /// Call once at app startup, before any `ensure_running()`.
 pub fn cleanup_orphaned_server(app: &AppHandle) {
     // Seed the OnceCell early so pid_file_path() works.
     let _ = APP_HANDLE.set(app.clone());
 
-    let path = match app.path().app_data_dir() {
-        Ok(d) => d.join("opencode-server.pid"),
-        Err(_) => return,
+    let Some(entries) = app
+        .path()
+        .app_data_dir()
+        .ok()
+        .and_then(|d| fs::read_dir(d).ok())
+    else {
+        return;
     };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with("opencode-server") && name.ends_with(".pid") {
+            cleanup_orphaned_pid_file(&entry.path());
+        }
+    }
+}
 
-    let content = match fs::read_to_string(&path) {
+fn cleanup_orphaned_pid_file(path: &std::path::Path) {
+    let content = match fs::read_to_string(path) {
         Ok(c) => c,
         Err(_) => return, // No PID file → nothing to clean up
     };
@@ -118,7 +588,7 @@ pub fn cleanup_orphaned_server(app: &AppHandle) {
     let record: ServerPidRecord = match serde_json::from_str(&content) {
         Ok(r) => r,
         Err(_) => {
-            let _ = fs::remove_file(&path);
+            let _ = fs::remove_file(path);
             return;
         }
     };
@@ -157,22 +627,40 @@ pub fn cleanup_orphaned_server(app: &AppHandle) {
         );
     }
 
-    let _ = fs::remove_file(&path);
+    let _ = fs::remove_file(path);
 }
 
-pub fn ensure_running(app: &AppHandle) -> Result<String, String> {
+pub fn ensure_running(app: &AppHandle, key: &ServerKey) -> Result<String, String> {
     // Cache the AppHandle for stop/release paths that don't have it.
     let _ = APP_HANDLE.set(app.clone());
+
+    // An external server is never spawned or managed by us. Don't fall back to
+    // spawning when it's down — that would silently ignore the user's config.
+    if let Some(external_url) = external_server_url(app) {
+        if is_healthy(&external_url) {
+            return Ok(external_url);
+        }
+        return Err(format!(
+            "Configured external OpenCode server at {external_url} is unreachable"
+        ));
+    }
+
     let hostname = DEFAULT_HOSTNAME.to_string();
-    let port = DEFAULT_PORT;
+    let port = server_port(key)?;
     let url = server_url(&hostname, port);
 
+    // A managed server that was healthy moments ago is still good.
+    if recently_healthy(&url) && managed_server_alive(key) {
+        return Ok(url);
+    }
+
     // If an unmanaged server is already running, use it.
     if is_healthy(&url) {
         return Ok(url);
     }
 
-    let mut guard = OPENCODE_SERVER
+    let slot = server_slot(key)?;
+    let mut guard = slot
         .lock()
         .map_err(|e| format!("OpenCode server lock error: {e}"))?;
 
@@ -185,7 +673,11 @@ pub fn ensure_running(app: &AppHandle) -> Result<String, String> {
                     return Ok(running_url);
                 }
             }
-            Ok(Some(_)) | Err(_) => {
+            Ok(Some(status)) => {
+                note_server_exit(key, proc_info, status);
+                *guard = None;
+            }
+            Err(_) => {
                 *guard = None;
             }
         }
@@ -199,23 +691,121 @@ pub fn ensure_running(app: &AppHandle) -> Result<String, String> {
         ));
     }
 
-    let mut cmd = silent_command(&cli_path);
-    cmd.arg("serve")
-        .arg("--hostname")
-        .arg(&hostname)
-        .arg("--port")
-        .arg(port.to_string())
-        .stdout(Stdio::null())
-        .stderr(Stdio::null());
+    check_spawn_breaker(key)?;
+
+    // A cold start can lose a port race or bind slowly on a busy machine, so
+    // retry health timeouts (but not spawn errors) with a fresh process.
+    let prefs = crate::load_preferences_sync(app).ok();
+    let retries = prefs
+        .as_ref()
+        .map(|p| p.opencode_server_spawn_retries)
+        .unwrap_or(2);
+    let memory_limit_mb = prefs
+        .as_ref()
+        .and_then(|p| p.opencode_server_memory_limit_mb)
+        .filter(|mb| *mb > 0);
+    let serve = ServeCommand::from_prefs(prefs.as_ref())?;
+    let attempts = retries + 1;
+    let result = spawn_until_healthy(
+        &mut guard,
+        &cli_path,
+        &hostname,
+        port,
+        attempts,
+        memory_limit_mb,
+        &serve,
+    );
+    record_spawn_result(app, key, &result);
+    result
+}
+
+/// Spawn a fresh managed server, retrying health timeouts up to `attempts` times.
+fn spawn_until_healthy(
+    guard: &mut Option<OpenCodeServerProcess>,
+    cli_path: &std::path::Path,
+    hostname: &str,
+    port: u16,
+    attempts: u32,
+    memory_limit_mb: Option<u32>,
+    serve: &ServeCommand,
+) -> Result<String, String> {
+    let url = server_url(hostname, port);
+    for attempt in 1..=attempts {
+        log::info!("Starting OpenCode server on {url} (attempt {attempt}/{attempts})");
+        let child = spawn_server(cli_path, hostname, port, memory_limit_mb, serve)?;
+
+        let server_pid = child.id();
+        *guard = Some(OpenCodeServerProcess {
+            child,
+            port,
+            hostname: hostname.to_string(),
+            memory_limit_mb,
+        });
+
+        // Write PID file so a future Jean instance can clean up if we crash.
+        write_pid_file(server_pid, port);
+
+        if wait_until_healthy(&url, 50) {
+            return Ok(url);
+        }
+
+        log::warn!("OpenCode server did not become healthy (attempt {attempt}/{attempts})");
+        if let Some(mut proc_info) = guard.take() {
+            kill_server_process(&mut proc_info);
+        }
+        remove_pid_file(port);
+        if attempt < attempts {
+            std::thread::sleep(Duration::from_millis(500));
+        }
+    }
+
+    Err("OpenCode server started but did not become healthy in time".to_string())
+}
+
+fn spawn_server(
+    cli_path: &std::path::Path,
+    hostname: &str,
+    port: u16,
+    memory_limit_mb: Option<u32>,
+    serve: &ServeCommand,
+) -> Result<Child, String> {
+    let (stdout, stderr) = server_log_stdio(port);
+    let mut cmd = silent_command(cli_path);
+    cmd.args(serve.args(hostname, port))
+        .stdout(stdout)
+        .stderr(stderr);
 
     #[cfg(unix)]
     {
         use std::os::unix::process::CommandExt;
         // Start in its own process group so we can terminate the full tree.
         cmd.process_group(0);
+
+        // Cap the data segment (heap) so a leak kills the server, not the machine.
+        // Not RLIMIT_AS: the Bun runtime reserves large virtual ranges at startup
+        // and would fail to launch under any realistic address-space cap.
+        if let Some(limit_mb) = memory_limit_mb {
+            log::info!("Limiting OpenCode server data segment to {limit_mb} MB");
+            let bytes = u64::from(limit_mb) * 1024 * 1024;
+            // SAFETY: setrlimit is async-signal-safe and touches no shared state.
+            unsafe {
+                cmd.pre_exec(move || {
+                    let limit = libc::rlimit {
+                        rlim_cur: bytes as libc::rlim_t,
+                        rlim_max: bytes as libc::rlim_t,
+                    };
+                    if libc::setrlimit(libc::RLIMIT_DATA, &limit) != 0 {
+                        return Err(std::io::Error::last_os_error());
+                    }
+                    Ok(())
+                });
+            }
+        }
     }
     #[cfg(windows)]
     {
+        // Resource limits are Unix-only.
+        let _ = memory_limit_mb;
         use std::os::windows::process::CommandExt;
         const CREATE_NEW_PROCESS_GROUP: u32 = 0x00000200;
         const CREATE_NO_WINDOW: u32 = 0x08000000;
@@ -223,52 +813,39 @@ pub fn ensure_running(app: &AppHandle) -> Result<String, String> {
         cmd.creation_flags(CREATE_NEW_PROCESS_GROUP | CREATE_NO_WINDOW);
     }
 
-    let child = cmd
-        .spawn()
-        .map_err(|e| format!("Failed to start OpenCode server: {e}"))?;
-
-    let server_pid = child.id();
-    *guard = Some(OpenCodeServerProcess {
-        child,
-        port,
-        hostname: hostname.clone(),
-    });
-
-    // Write PID file so a future Jean instance can clean up if we crash.
-    write_pid_file(server_pid, port);
-
-    if !wait_until_healthy(&url, 50) {
-        return Err("OpenCode server started but did not become healthy in time".to_string());
-    }
-
-    Ok(url)
+    cmd.spawn()
+        .map_err(|e| format!("Failed to start OpenCode server: {e}"))
 }
 
-/// Increment usage count and ensure the server is running. Returns the base URL.
-/// Each `acquire` must be paired with a `release` when the caller is done.
-pub fn acquire(app: &AppHandle) -> Result<String, String> {
-    USAGE_COUNT.fetch_add(1, Ordering::SeqCst);
-    match ensure_running(app) {
+/// Increment `key`'s usage count and ensure its server is running. Returns the
+/// base URL. Each `acquire` must be paired with a `release` of the same key.
+pub fn acquire(app: &AppHandle, key: &ServerKey) -> Result<String, String> {
+    add_user(key)?;
+    match ensure_running(app, key) {
         Ok(url) => Ok(url),
         Err(e) => {
             // Roll back on failure so we don't leave a phantom user.
-            USAGE_COUNT.fetch_sub(1, Ordering::SeqCst);
+            update_usage(key, false);
             Err(e)
         }
     }
 }
 
-/// Decrement usage count. If this was the last user, schedule a delayed shutdown.
-/// The delay prevents killing the server during the brief window between sequential
-/// operations (e.g., naming finishes just before chat sends its next request).
-pub fn release() {
-    let prev = USAGE_COUNT.fetch_sub(1, Ordering::SeqCst);
-    if prev == 1 {
+/// Decrement `key`'s usage count. If this was the last user, schedule a delayed
+/// shutdown of that server. The delay prevents killing the server during the
+/// brief window between sequential operations (e.g., naming finishes just before
+/// chat sends its next request).
+pub fn release(key: &ServerKey) {
+    let prev = update_usage(key, false);
+    if prev == 1 && DRAINING.load(Ordering::SeqCst) {
+        stop_drained_server(key);
+    } else if prev == 1 {
         // Schedule delayed shutdown — if no one re-acquires within 10min, stop the server.
-        std::thread::spawn(|| {
+        let key = key.clone();
+        std::thread::spawn(move || {
             std::thread::sleep(Duration::from_secs(600));
-            if USAGE_COUNT.load(Ordering::SeqCst) == 0 {
-                if let Err(e) = stop_managed_server_inner() {
+            if usage_count(&key) == 0 {
+                if let Err(e) = stop_managed_server_inner(&key) {
                     log::warn!("Failed to stop managed OpenCode server on last release: {e}");
                 }
             }
@@ -276,106 +853,715 @@ pub fn release() {
     }
 }
 
-fn stop_managed_server_inner() -> Result<bool, String> {
-    let mut guard = OPENCODE_SERVER
-        .lock()
-        .map_err(|e| format!("OpenCode server lock error: {e}"))?;
+/// Reject new acquires and stop each managed server once its in-flight prompts
+/// finish. Idle servers stop immediately.
+pub fn begin_drain() {
+    DRAINING.store(true, Ordering::SeqCst);
+    log::info!(
+        "Draining OpenCode servers ({} active consumers)",
+        total_usage()
+    );
+    for key in server_keys() {
+        if usage_count(&key) == 0 {
+            stop_drained_server(&key);
+        }
+    }
+}
 
-    let Some(proc_info) = guard.as_mut() else {
-        return Ok(false);
+/// Accept acquires again. A server already stopped by the drain is respawned
+/// on the next prompt.
+pub fn end_drain() {
+    if DRAINING.swap(false, Ordering::SeqCst) {
+        log::info!("OpenCode server drain ended");
+    }
+}
+
+fn stop_drained_server(key: &ServerKey) {
+    match stop_managed_server_inner(key) {
+        Ok(true) => log::info!("Drained OpenCode server stopped ({key:?})"),
+        Ok(false) => {}
+        Err(e) => log::warn!("Failed to stop drained OpenCode server: {e}"),
+    }
+}
+
+fn stop_managed_server_inner(key: &ServerKey) -> Result<bool, String> {
+    let slot = server_slot(key)?;
+    let stopped = {
+        let mut guard = slot
+            .lock()
+            .map_err(|e| format!("OpenCode server lock error: {e}"))?;
+        match guard.as_mut() {
+            Some(proc_info) => {
+                let port = proc_info.port;
+                kill_server_process(proc_info);
+                *guard = None;
+                remove_pid_file(port);
+                true
+            }
+            None => false,
+        }
     };
+    forget_idle_server(key, slot);
+    Ok(stopped)
+}
 
+/// Drop the per-key state of a stopped project server nobody is using, so
+/// projects that are opened once don't keep a port, slot and counters for the
+/// rest of the session. The shared server's entries are kept, as is a pending
+/// memory-limit note, which the next prompt on that project still reports.
+fn forget_idle_server(key: &ServerKey, slot: ServerSlot) {
+    if *key == ServerKey::Shared {
+        return;
+    }
+    let Ok(mut counts) = USAGE_COUNTS.lock() else {
+        return;
+    };
+    if counts.get(key).is_some_and(|count| *count > 0) {
+        return;
+    }
+    let Ok(mut servers) = OPENCODE_SERVERS.lock() else {
+        return;
+    };
+    // Another caller holding the slot may be about to start a server in it.
+    if Arc::strong_count(&slot) > 2 {
+        return;
+    }
+    servers.remove(key);
+    counts.remove(key);
+    if let Ok(mut ports) = SERVER_PORTS.lock() {
+        ports.remove(key);
+    }
+    if let Ok(mut breakers) = SPAWN_BREAKERS.lock() {
+        breakers.remove(key);
+    }
+}
+
+/// Stop every managed server. Returns whether any was running.
+fn stop_all_managed_servers() -> Result<bool, String> {
+    let mut stopped_any = false;
+    for key in server_keys() {
+        stopped_any |= stop_managed_server_inner(&key)?;
+    }
+    Ok(stopped_any)
+}
+
+fn kill_server_process(proc_info: &mut OpenCodeServerProcess) {
+    invalidate_health_cache(&server_url(&proc_info.hostname, proc_info.port));
     let pid = proc_info.child.id();
     let _ = crate::platform::kill_process_tree(pid);
     // Fallback direct child kill in case tree-kill is unsupported/fails.
     let _ = proc_info.child.kill();
     let _ = proc_info.child.wait();
-    *guard = None;
-    remove_pid_file();
-    Ok(true)
 }
 
-/// Get the current server URL without incrementing the usage count.
-/// Returns `None` if no server is running (managed or unmanaged).
-pub fn get_current_url() -> Option<String> {
-    let url = server_url(DEFAULT_HOSTNAME, DEFAULT_PORT);
+/// Record a managed server's exit. When it ran under a memory limit and died the
+/// way an allocation failure does, the explanation is kept for
+/// `take_memory_limit_exit` to surface. Other crashes are only logged.
+fn note_server_exit(key: &ServerKey, proc_info: &OpenCodeServerProcess, status: ExitStatus) {
+    let Some(limit_mb) = proc_info.memory_limit_mb else {
+        return;
+    };
+    let log_tail = log_file_path(proc_info.port)
+        .and_then(|path| read_log_tail(&path))
+        .unwrap_or_default();
+    if !killed_by_memory_limit(status, &log_tail) {
+        if !status.success() {
+            log::warn!("OpenCode server exited ({status})");
+        }
+        return;
+    }
+    let message = format!(
+        "OpenCode server exited ({status}); it may have exceeded the {limit_mb} MB memory limit. Raise the limit in Settings if this keeps happening."
+    );
+    log::warn!("{message}");
+    if let Ok(mut exits) = MEMORY_LIMIT_EXITS.lock() {
+        exits.insert(key.clone(), message);
+    }
+}
+
+/// How much of the end of the server log to scan for allocation failures.
+const LOG_TAIL_BYTES: u64 = 8 * 1024;
+
+fn read_log_tail(path: &std::path::Path) -> Option<String> {
+    use std::io::{Read, Seek, SeekFrom};
+    let mut file = fs::File::open(path).ok()?;
+    let len = file.metadata().ok()?.len();
+    file.seek(SeekFrom::Start(len.saturating_sub(LOG_TAIL_BYTES)))
+        .ok()?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes).ok()?;
+    Some(String::from_utf8_lossy(&bytes).into_owned())
+}
 
-    // Check managed process first
-    if let Ok(mut guard) = OPENCODE_SERVER.lock() {
-        if let Some(proc) = guard.as_mut() {
-            if matches!(proc.child.try_wait(), Ok(None)) {
-                return Some(server_url(&proc.hostname, proc.port));
+/// Whether an exit looks like the memory limit: killed by SIGKILL/SIGSEGV (how
+/// a runtime dies when an allocation past the cap fails), or the log ends with
+/// an out-of-memory error.
+fn killed_by_memory_limit(status: ExitStatus, log_tail: &str) -> bool {
+    if status.success() {
+        return false;
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::ExitStatusExt;
+        if matches!(status.signal(), Some(libc::SIGKILL | libc::SIGSEGV)) {
+            return true;
+        }
+    }
+    mentions_out_of_memory(log_tail)
+}
+
+/// Allocation-failure wording from the OS, Bun/JavaScriptCore and Node-style runtimes.
+fn mentions_out_of_memory(text: &str) -> bool {
+    const SIGNATURES: &[&str] = &[
+        "enomem",
+        "cannot allocate memory",
+        "out of memory",
+        "outofmemory",
+        "allocation failed",
+    ];
+    let text = text.to_ascii_lowercase();
+    SIGNATURES.iter().any(|s| text.contains(s))
+}
+
+/// Explanation for `key`'s managed server having been stopped by its memory
+/// limit, if that happened since the last call. Reaps an exited process first
+/// so a server that died mid-prompt is reported right away.
+pub fn take_memory_limit_exit(key: &ServerKey) -> Option<String> {
+    if let Ok(slot) = server_slot(key) {
+        if let Ok(mut guard) = slot.lock() {
+            if let Some(proc_info) = guard.as_mut() {
+                if let Ok(Some(status)) = proc_info.child.try_wait() {
+                    note_server_exit(key, proc_info, status);
+                    *guard = None;
+                }
             }
         }
     }
+    MEMORY_LIMIT_EXITS.lock().ok()?.remove(key)
+}
 
-    // Fall back to checking if an unmanaged server is healthy on the default port
-    if is_healthy(&url) {
-        return Some(url);
+fn managed_server_alive(key: &ServerKey) -> bool {
+    let Ok(slot) = server_slot(key) else {
+        return false;
+    };
+    let alive = slot
+        .lock()
+        .ok()
+        .and_then(|mut guard| {
+            guard
+                .as_mut()
+                .map(|p| matches!(p.child.try_wait(), Ok(None)))
+        })
+        .unwrap_or(false);
+    alive
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OpenCodeLogLine {
+    pub line: String,
+}
+
+fn log_tail_key() -> ServerKey {
+    LOG_TAIL_KEY
+        .lock()
+        .map(|key| key.clone())
+        .unwrap_or(ServerKey::Shared)
+}
+
+/// Emit `opencode-server:log` for each line appended to the selected server's
+/// log until the subscriber goes away or that server stops.
+fn run_log_tailer(app: AppHandle) {
+    let mut current: Option<(ServerKey, crate::chat::tail::NdjsonTailer)> = None;
+
+    while LOG_SUBSCRIBED.load(Ordering::SeqCst) {
+        let key = log_tail_key();
+        if !managed_server_alive(&key) {
+            break;
+        }
+        if current.as_ref().is_none_or(|(k, _)| *k != key) {
+            let opened = assigned_port(&key)
+                .and_then(log_file_path)
+                .ok_or_else(|| "Could not resolve OpenCode server log path".to_string())
+                .and_then(|path| crate::chat::tail::NdjsonTailer::new_at_end(&path));
+            match opened {
+                Ok(tailer) => current = Some((key, tailer)),
+                Err(e) => {
+                    log::warn!("Failed to tail OpenCode server log: {e}");
+                    break;
+                }
+            }
+        }
+        let Some((_, tailer)) = current.as_mut() else {
+            break;
+        };
+        match tailer.poll() {
+            Ok(lines) => {
+                for line in lines {
+                    let _ = app.emit_all("opencode-server:log", &OpenCodeLogLine { line });
+                }
+            }
+            Err(e) => {
+                log::warn!("Error reading OpenCode server log: {e}");
+                break;
+            }
+        }
+        std::thread::sleep(Duration::from_millis(250));
     }
 
-    None
+    LOG_TAILER_RUNNING.store(false, Ordering::SeqCst);
+    log::trace!("OpenCode server log tailer stopped");
 }
 
-/// Stop Jean-managed OpenCode server process during app lifecycle shutdown.
+/// Stop Jean-managed OpenCode server processes during app lifecycle shutdown.
 pub fn shutdown_managed_server() -> Result<bool, String> {
-    stop_managed_server_inner()
+    stop_all_managed_servers()
 }
 
+/// Wait for a termination signal and shut down managed servers before exiting.
+///
+/// Tauri's exit hooks only run for a graceful app exit, so a `kill` or Ctrl+C
+/// would otherwise leave the OpenCode server's process group running.
+pub async fn shutdown_on_termination_signal() {
+    if let Err(e) = wait_for_termination_signal().await {
+        log::warn!("Failed to install termination signal handler: {e}");
+        return;
+    }
+    log::info!("Termination signal received, shutting down managed servers");
+    let _ = tokio::task::spawn_blocking(|| {
+        crate::terminal::cleanup_all_terminals();
+        if let Err(e) = shutdown_managed_server() {
+            log::warn!("Failed to stop OpenCode server on signal: {e}");
+        }
+        crate::chat::codex_server::shutdown_server();
+    })
+    .await;
+    std::process::exit(0);
+}
+
+#[cfg(unix)]
+async fn wait_for_termination_signal() -> std::io::Result<()> {
+    use tokio::signal::unix::{signal, SignalKind};
+    let mut terminate = signal(SignalKind::terminate())?;
+    let mut interrupt = signal(SignalKind::interrupt())?;
+    tokio::select! {
+        _ = terminate.recv() => {}
+        _ = interrupt.recv() => {}
+    }
+    Ok(())
+}
+
+#[cfg(windows)]
+async fn wait_for_termination_signal() -> std::io::Result<()> {
+    use tokio::signal::windows::{ctrl_c, ctrl_close, ctrl_shutdown};
+    let mut ctrl_c = ctrl_c()?;
+    let mut close = ctrl_close()?;
+    let mut shutdown = ctrl_shutdown()?;
+    tokio::select! {
+        _ = ctrl_c.recv() => {}
+        _ = close.recv() => {}
+        _ = shutdown.recv() => {}
+    }
+    Ok(())
+}
+
+/// Start the server serving `worktree_id`, or the shared one when omitted.
 #[tauri::command]
-pub async fn start_opencode_server(app: AppHandle) -> Result<OpenCodeServerStatus, String> {
-    let url = ensure_running(&app)?;
+pub async fn start_opencode_server(
+    app: AppHandle,
+    worktree_id: Option<String>,
+) -> Result<OpenCodeServerStatus, String> {
+    let key = ServerKey::for_request(&app, worktree_id.as_deref());
+    let url = ensure_running(&app, &key)?;
+    if external_server_url(&app).is_some() {
+        let (hostname, port) = url_host_port(&url);
+        return Ok(OpenCodeServerStatus {
+            running: true,
+            process_alive: true,
+            url: Some(url),
+            port,
+            hostname,
+            managed: false,
+            draining: false,
+        });
+    }
     Ok(OpenCodeServerStatus {
         running: true,
+        process_alive: true,
         url: Some(url),
-        port: Some(DEFAULT_PORT),
+        port: assigned_port(&key),
         hostname: Some(DEFAULT_HOSTNAME.to_string()),
         managed: true,
+        draining: DRAINING.load(Ordering::SeqCst),
     })
 }
 
 #[tauri::command]
 pub async fn stop_opencode_server() -> Result<(), String> {
-    let _ = stop_managed_server_inner()?;
+    let _ = stop_all_managed_servers()?;
+    Ok(())
+}
+
+/// Start streaming a managed server's log as `opencode-server:log` events: the
+/// server serving `worktree_id`, or the shared one when omitted. A call while a
+/// tailer is active switches it to the requested server.
+#[tauri::command]
+pub async fn tail_opencode_log(app: AppHandle, worktree_id: Option<String>) -> Result<(), String> {
+    let _ = APP_HANDLE.set(app.clone());
+    let key = ServerKey::for_request(&app, worktree_id.as_deref());
+    if !managed_server_alive(&key) {
+        return Err("No Jean-managed OpenCode server is running".to_string());
+    }
+    *LOG_TAIL_KEY
+        .lock()
+        .map_err(|e| format!("OpenCode log tailer lock error: {e}"))? = key;
+
+    LOG_SUBSCRIBED.store(true, Ordering::SeqCst);
+    if LOG_TAILER_RUNNING
+        .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+        .is_err()
+    {
+        return Ok(());
+    }
+    std::thread::spawn(move || run_log_tailer(app));
+    Ok(())
+}
+
+/// Put the managed servers into draining mode (see `begin_drain`).
+#[tauri::command]
+pub async fn begin_opencode_server_drain() -> Result<(), String> {
+    begin_drain();
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn end_opencode_server_drain() -> Result<(), String> {
+    end_drain();
+    Ok(())
+}
+
+/// Stop streaming the server log. The tailer thread exits on its next poll.
+#[tauri::command]
+pub async fn untail_opencode_log() -> Result<(), String> {
+    LOG_SUBSCRIBED.store(false, Ordering::SeqCst);
     Ok(())
 }
 
+/// Check whether anything is listening on the configured server's host/port with
+/// a bare TCP connect. Never spawns and sends no HTTP, so it is cheap to poll;
+/// `reachable && !running` in the status means the server is up but unhealthy.
+/// `worktree_id` selects that worktree's server; omitted means the shared one.
 #[tauri::command]
-pub async fn get_opencode_server_status() -> Result<OpenCodeServerStatus, String> {
+pub async fn probe_opencode_port(
+    app: AppHandle,
+    worktree_id: Option<String>,
+) -> Result<OpenCodePortProbe, String> {
+    let (hostname, port) = match external_server_url(&app) {
+        Some(url) => match url_host_port(&url) {
+            (Some(hostname), Some(port)) => (hostname, port),
+            _ => return Err(format!("Invalid external OpenCode server URL: {url}")),
+        },
+        None => {
+            let key = ServerKey::for_request(&app, worktree_id.as_deref());
+            let port = assigned_port(&key)
+                .ok_or("No OpenCode server has been started for this project")?;
+            (DEFAULT_HOSTNAME.to_string(), port)
+        }
+    };
+
+    // Name resolution and connect are blocking
+    let probe_host = hostname.clone();
+    let reachable = tokio::task::spawn_blocking(move || {
+        use std::net::ToSocketAddrs;
+        (probe_host.as_str(), port)
+            .to_socket_addrs()
+            .map(|mut addrs| {
+                addrs.any(|addr| {
+                    std::net::TcpStream::connect_timeout(&addr, PORT_PROBE_TIMEOUT).is_ok()
+                })
+            })
+            .unwrap_or(false)
+    })
+    .await
+    .map_err(|e| format!("OpenCode port probe failed: {e}"))?;
+
+    Ok(OpenCodePortProbe {
+        hostname,
+        port,
+        reachable,
+    })
+}
+
+/// Status of the server serving `worktree_id`, or of the shared server when omitted.
+#[tauri::command]
+pub async fn get_opencode_server_status(
+    app: AppHandle,
+    worktree_id: Option<String>,
+) -> Result<OpenCodeServerStatus, String> {
+    if let Some(external_url) = external_server_url(&app) {
+        let (hostname, port) = url_host_port(&external_url);
+        // We can't see an external server's process; reachability is all we know.
+        let healthy = is_healthy(&external_url);
+        return Ok(OpenCodeServerStatus {
+            running: healthy,
+            process_alive: healthy,
+            url: Some(external_url),
+            port,
+            hostname,
+            managed: false,
+            draining: false,
+        });
+    }
+
+    let key = ServerKey::for_request(&app, worktree_id.as_deref());
+    let Some(port) = assigned_port(&key) else {
+        // No port yet means this project's server was never started.
+        return Ok(OpenCodeServerStatus {
+            running: false,
+            process_alive: false,
+            url: None,
+            port: None,
+            hostname: None,
+            managed: false,
+            draining: DRAINING.load(Ordering::SeqCst),
+        });
+    };
+
     let mut managed_running = false;
     {
-        let mut guard = OPENCODE_SERVER
+        let slot = server_slot(&key)?;
+        let mut guard = slot
             .lock()
             .map_err(|e| format!("OpenCode server lock error: {e}"))?;
 
         if let Some(proc_info) = guard.as_mut() {
-            managed_running = matches!(proc_info.child.try_wait(), Ok(None));
+            let exit = proc_info.child.try_wait();
+            managed_running = matches!(exit, Ok(None));
+            if let Ok(Some(status)) = exit {
+                note_server_exit(&key, proc_info, status);
+            }
             if !managed_running {
                 *guard = None;
             }
         }
     }
 
-    let url = server_url(DEFAULT_HOSTNAME, DEFAULT_PORT);
+    let url = server_url(DEFAULT_HOSTNAME, port);
     let healthy = is_healthy(&url);
+    let process_alive = managed_running || healthy;
+    if managed_running && !healthy {
+        log::warn!("Managed OpenCode server process is alive but failing health checks");
+    }
 
     Ok(OpenCodeServerStatus {
-        running: managed_running || healthy,
-        url: if managed_running || healthy {
-            Some(url)
-        } else {
-            None
-        },
-        port: if managed_running || healthy {
-            Some(DEFAULT_PORT)
-        } else {
-            None
-        },
-        hostname: if managed_running || healthy {
+        running: healthy,
+        process_alive,
+        url: if process_alive { Some(url) } else { None },
+        port: if process_alive { Some(port) } else { None },
+        hostname: if process_alive {
             Some(DEFAULT_HOSTNAME.to_string())
         } else {
             None
         },
         managed: managed_running,
+        draining: DRAINING.load(Ordering::SeqCst),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn out_of_memory_wording_is_recognized() {
+        assert!(mentions_out_of_memory("error: ENOMEM: not enough memory"));
+        assert!(mentions_out_of_memory("mmap: Cannot allocate memory"));
+        assert!(mentions_out_of_memory("RangeError: Out of memory"));
+        assert!(!mentions_out_of_memory("error: unknown flag --prot"));
+        assert!(!mentions_out_of_memory(""));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn only_memory_style_exits_are_blamed_on_the_limit() {
+        use std::os::unix::process::ExitStatusExt;
+        let exited = |code: i32| ExitStatus::from_raw(code << 8);
+        let signaled = |signal: i32| ExitStatus::from_raw(signal);
+
+        assert!(killed_by_memory_limit(signaled(libc::SIGKILL), ""));
+        assert!(killed_by_memory_limit(signaled(libc::SIGSEGV), ""));
+        assert!(killed_by_memory_limit(exited(1), "FATAL: out of memory"));
+        assert!(!killed_by_memory_limit(exited(1), "unknown option --foo"));
+        assert!(!killed_by_memory_limit(signaled(libc::SIGTERM), ""));
+        assert!(!killed_by_memory_limit(exited(0), "out of memory"));
+    }
+
+    fn serve_prefs(subcommand: Option<&str>, extra_args: &[&str]) -> crate::AppPreferences {
+        crate::AppPreferences {
+            opencode_serve_subcommand: subcommand.map(str::to_string),
+            opencode_extra_serve_args: extra_args.iter().map(|a| a.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn serve_command_rejects_host_and_port_overrides() {
+        for arg in ["--port", "--port=5000", "--hostname", "--hostname=0.0.0.0"] {
+            let prefs = serve_prefs(None, &["--print-logs", arg]);
+            let err = ServeCommand::from_prefs(Some(&prefs)).unwrap_err();
+            assert!(err.contains(arg), "{arg}: {err}");
+        }
+        // Only the exact flags are reserved.
+        let prefs = serve_prefs(None, &["--portal", "--hostnames"]);
+        assert!(ServeCommand::from_prefs(Some(&prefs)).is_ok());
+    }
+
+    #[test]
+    fn serve_command_defaults_to_serve() {
+        for subcommand in [None, Some(""), Some("   ")] {
+            let prefs = serve_prefs(subcommand, &[]);
+            let serve = ServeCommand::from_prefs(Some(&prefs)).unwrap();
+            assert_eq!(serve.subcommand, "serve", "{subcommand:?}");
+        }
+        assert_eq!(ServeCommand::from_prefs(None).unwrap().subcommand, "serve");
+
+        let prefs = serve_prefs(Some(" web "), &[]);
+        let serve = ServeCommand::from_prefs(Some(&prefs)).unwrap();
+        assert_eq!(serve.subcommand, "web");
+    }
+
+    #[test]
+    fn serve_command_drops_blank_extra_args() {
+        let prefs = serve_prefs(None, &["", "--print-logs", "  ", "--log-level=DEBUG"]);
+        let serve = ServeCommand::from_prefs(Some(&prefs)).unwrap();
+        assert_eq!(
+            serve.args("127.0.0.1", 4097),
+            [
+                "serve",
+                "--hostname",
+                "127.0.0.1",
+                "--port",
+                "4097",
+                "--print-logs",
+                "--log-level=DEBUG"
+            ]
+        );
+    }
+
+    fn fail(breakers: &mut HashMap<ServerKey, SpawnBreaker>, now: Instant) -> Option<String> {
+        let error = Err("health check timed out".to_string());
+        record_spawn_outcome(breakers, &ServerKey::Shared, &error, now)
+    }
+
+    #[test]
+    fn breaker_trips_after_threshold_failures() {
+        let mut breakers = HashMap::new();
+        let start = Instant::now();
+        for i in 1..BREAKER_THRESHOLD {
+            let now = start + Duration::from_secs(i.into());
+            assert_eq!(fail(&mut breakers, now), None);
+        }
+        let message = fail(&mut breakers, start + Duration::from_secs(10)).unwrap();
+        assert!(message.contains("health check timed out"), "{message}");
+
+        let breaker = breakers.get_mut(&ServerKey::Shared).unwrap();
+        let err = breaker.check(start + Duration::from_secs(11)).unwrap_err();
+        assert!(err.contains("retrying in"), "{err}");
+    }
+
+    #[test]
+    fn breaker_forgets_failures_outside_the_window() {
+        let mut breakers = HashMap::new();
+        let start = Instant::now();
+        for _ in 1..BREAKER_THRESHOLD {
+            fail(&mut breakers, start);
+        }
+        let later = start + BREAKER_WINDOW + Duration::from_secs(1);
+        assert_eq!(fail(&mut breakers, later), None);
+    }
+
+    #[test]
+    fn success_resets_the_breaker() {
+        let mut breakers = HashMap::new();
+        let start = Instant::now();
+        for _ in 1..BREAKER_THRESHOLD {
+            fail(&mut breakers, start);
+        }
+        let ok = Ok("http://127.0.0.1:4096".to_string());
+        assert_eq!(
+            record_spawn_outcome(&mut breakers, &ServerKey::Shared, &ok, start),
+            None
+        );
+        assert!(breakers.is_empty());
+        assert_eq!(fail(&mut breakers, start), None);
+    }
+
+    #[test]
+    fn breaker_allows_one_retry_after_cooldown_then_retrips() {
+        let mut breakers = HashMap::new();
+        let start = Instant::now();
+        for _ in 0..BREAKER_THRESHOLD {
+            fail(&mut breakers, start);
+        }
+
+        let reopened = start + BREAKER_COOLDOWN + Duration::from_secs(1);
+        let breaker = breakers.get_mut(&ServerKey::Shared).unwrap();
+        assert!(breaker.check(start + Duration::from_secs(1)).is_err());
+        assert!(breaker.check(reopened).is_ok());
+
+        // The half-open retry fails: one failure is enough to trip again.
+        assert!(fail(&mut breakers, reopened).is_some());
+        let breaker = breakers.get_mut(&ServerKey::Shared).unwrap();
+        assert!(breaker.check(reopened).is_err());
+    }
+
+    #[test]
+    fn server_key_resolves_per_project_only_when_enabled() {
+        assert_eq!(ServerKey::resolve(false, Some("p1")), ServerKey::Shared);
+        assert_eq!(
+            ServerKey::resolve(true, Some("p1")),
+            ServerKey::Project("p1".to_string())
+        );
+        // An unknown worktree falls back to the shared server.
+        assert_eq!(ServerKey::resolve(true, None), ServerKey::Shared);
+        assert_eq!(ServerKey::resolve(false, None), ServerKey::Shared);
+    }
+
+    #[test]
+    fn project_ports_skip_assigned_and_busy_ports() {
+        let mut assigned = HashMap::new();
+        assert_eq!(
+            next_project_port(&assigned, |_| true),
+            Some(DEFAULT_PORT + 1)
+        );
+
+        assigned.insert(ServerKey::Project("a".to_string()), DEFAULT_PORT + 1);
+        assert_eq!(
+            next_project_port(&assigned, |_| true),
+            Some(DEFAULT_PORT + 2)
+        );
+
+        // A port bound by something outside Jean is skipped too.
+        let busy = DEFAULT_PORT + 2;
+        assert_eq!(
+            next_project_port(&assigned, |port| port != busy),
+            Some(DEFAULT_PORT + 3)
+        );
+
+        // A freed port is handed out again.
+        assigned.clear();
+        assert_eq!(
+            next_project_port(&assigned, |_| true),
+            Some(DEFAULT_PORT + 1)
+        );
+    }
+
+    #[test]
+    fn project_ports_run_out_at_the_end_of_the_range() {
+        let assigned = HashMap::new();
+        assert_eq!(next_project_port(&assigned, |_| false), None);
+        assert_eq!(
+            next_project_port(&assigned, |port| port == DEFAULT_PORT + PROJECT_PORT_RANGE),
+            Some(DEFAULT_PORT + PROJECT_PORT_RANGE)
+        );
+    }
+}
@@ -6558,24 +6558,19 @@ fn execute_codex_review(
     std::fs::write(&schema_file, REVIEW_SCHEMA)
         .map_err(|e| format!("Failed to write schema file: {e}"))?;
 
+    let (actual_model, is_fast) = crate::chat::codex::one_shot_model(model);
     let mut cmd = crate::platform::silent_command(&cli_path);
-    cmd.args([
-        "exec",
-        "--json",
-        "--model",
-        model,
-        "--full-auto",
-        "--output-schema",
-    ]);
-    cmd.arg(&schema_file);
+    cmd.args(crate::chat::codex::build_one_shot_exec_args(
+        &actual_model,
+        is_fast,
+        &schema_file,
+        working_dir,
+        crate::chat::codex::needs_skip_git_check(working_dir),
+        crate::chat::codex::model_provider_pref(app).as_deref(),
+    ));
     if let Some(dir) = working_dir {
-        cmd.arg("--cd");
-        cmd.arg(dir);
         cmd.current_dir(dir);
-    } else {
-        cmd.arg("--skip-git-repo-check");
     }
-    cmd.arg("-");
     cmd.stdin(std::process::Stdio::piped())
         .stdout(std::process::Stdio::piped())
         .stderr(std::process::Stdio::piped());
@@ -786,6 +786,18 @@ function App() {
                     store.addToolBlock(session.session_id, block.tool_call_id)
                   } else if (block.type === 'thinking') {
                     store.addThinkingBlock(session.session_id, block.thinking)
+                  } else if (block.type === 'error') {
+                    store.addErrorBlock(
+                      session.session_id,
+                      block.tool_call_id,
+                      block.message
+                    )
+                  } else if (block.type === 'todo') {
+                    store.setTodoBlock(
+                      session.session_id,
+                      block.id,
+                      block.items
+                    )
                   }
                 }
 
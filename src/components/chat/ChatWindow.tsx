@@ -859,6 +859,7 @@ export function ChatWindow({
     activeSessionId,
     isSending,
     currentToolCalls,
+    currentContentBlocks: currentStreamingContentBlocks,
     lastAssistantMessage,
   })
 
@@ -305,6 +305,12 @@ export const StreamingMessage = memo(function StreamingMessage({
                               </div>
                             )
                           }
+                          case 'toolError':
+                            return (
+                              <div className="text-xs text-destructive border border-destructive/30 bg-destructive/10 rounded px-2 py-1">
+                                {item.message}
+                              </div>
+                            )
                           case 'unknown':
                             return (
                               <div className="text-xs text-muted-foreground border rounded px-2 py-1">
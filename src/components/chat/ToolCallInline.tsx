@@ -24,11 +24,13 @@ import {
   Image as ImageIcon,
 } from 'lucide-react'
 import { diffLines } from 'diff'
+import { toast } from 'sonner'
 import type { ToolCall } from '@/types/chat'
 import type { StackableItem } from './tool-call-utils'
 import { Markdown } from '@/components/ui/markdown'
 import { cn } from '@/lib/utils'
 import { getFilename } from '@/lib/path-utils'
+import { invoke } from '@/lib/transport'
 import {
   Collapsible,
   CollapsibleContent,
@@ -58,9 +60,28 @@ export function ToolCallInline({
   isIncomplete,
 }: ToolCallInlineProps) {
   const [isOpen, setIsOpen] = useState(false)
+  const [fullOutput, setFullOutput] = useState<string | null>(null)
+  const [isLoadingFullOutput, setIsLoadingFullOutput] = useState(false)
   const { icon, label, detail, filePath, expandedContent } =
     getToolDisplay(toolCall)
 
+  // Output was truncated; the backend kept the rest in a file
+  const handleLoadFullOutput = async () => {
+    if (!toolCall.full_output_path) return
+    setIsLoadingFullOutput(true)
+    try {
+      setFullOutput(
+        await invoke<string>('read_tool_output', {
+          path: toolCall.full_output_path,
+        })
+      )
+    } catch {
+      toast.error('Failed to load full tool output')
+    } finally {
+      setIsLoadingFullOutput(false)
+    }
+  }
+
   const handleFileClick = (e: React.MouseEvent) => {
     e.stopPropagation()
     if (filePath && onFileClick) {
@@ -125,8 +146,18 @@ export function ToolCallInline({
                   Output:
                 </div>
                 <pre className="max-h-64 overflow-auto whitespace-pre-wrap text-xs font-mono text-foreground/80 bg-muted/50 rounded p-2">
-                  {toolCall.output}
+                  {fullOutput ?? toolCall.output}
                 </pre>
+                {toolCall.full_output_path && fullOutput === null && (
+                  <button
+                    type="button"
+                    onClick={handleLoadFullOutput}
+                    disabled={isLoadingFullOutput}
+                    className="mt-1 text-xs text-primary hover:underline disabled:opacity-50"
+                  >
+                    {isLoadingFullOutput ? 'Loading…' : 'Show full output'}
+                  </button>
+                )}
               </>
             )}
           </div>
@@ -532,13 +563,37 @@ function DiffView({
   )
 }
 
-/** A single Codex file change entry */
+/** A single Codex file change entry, as normalized by the backend */
 interface CodexFileChange {
   diff?: string
-  kind?: { type?: string; move_path?: string | null }
+  /** Object form comes from sessions saved before the backend normalized it */
+  kind?:
+    | 'created'
+    | 'modified'
+    | 'deleted'
+    | { type?: string; move_path?: string | null }
+  move_path?: string | null
   path?: string
 }
 
+/** Change label and rename target for either the normalized or raw shape */
+function describeFileChange(change: CodexFileChange): {
+  kind: string
+  movePath?: string | null
+} {
+  if (typeof change.kind === 'string') {
+    return { kind: change.kind, movePath: change.move_path }
+  }
+  const rawType = change.kind?.type
+  const kind =
+    rawType === 'add'
+      ? 'created'
+      : rawType === 'delete'
+        ? 'deleted'
+        : 'modified'
+  return { kind, movePath: change.kind?.move_path ?? change.move_path }
+}
+
 /** Renders a pre-computed unified diff patch with colored +/- lines */
 function PatchDiffView({ patch }: { patch: string }) {
   const lines = patch.split('\n')
@@ -583,13 +638,13 @@ function FileChangeDiffView({ input }: { input: unknown }) {
     <div className="space-y-3">
       {changes.map((change, idx) => {
         const filename = change.path ? getFilename(change.path) : `file ${idx + 1}`
-        const changeType = change.kind?.type ?? 'update'
+        const { kind: changeType, movePath } = describeFileChange(change)
         const typeColor =
-          changeType === 'create'
+          changeType === 'created'
             ? 'text-green-500'
-            : changeType === 'delete'
+            : changeType === 'deleted'
               ? 'text-red-500'
-              : changeType === 'rename'
+              : movePath
                 ? 'text-yellow-500'
                 : 'text-blue-500'
 
@@ -605,9 +660,9 @@ function FileChangeDiffView({ input }: { input: unknown }) {
               >
                 {changeType}
               </span>
-              {change.kind?.move_path && (
+              {movePath && (
                 <span className="text-muted-foreground/60 truncate">
-                  → {getFilename(change.kind.move_path)}
+                  → {getFilename(movePath)}
                 </span>
               )}
             </div>
@@ -1,14 +1,38 @@
 import { useEffect, useMemo, useState } from 'react'
 import { isTodoWrite } from '@/types/chat'
-import type { ToolCall, ChatMessage, CodexAgent } from '@/types/chat'
+import type {
+  ToolCall,
+  ChatMessage,
+  CodexAgent,
+  ContentBlock,
+  Todo,
+} from '@/types/chat'
 
 interface UseActiveTodosAndAgentsParams {
   activeSessionId: string | null | undefined
   isSending: boolean
   currentToolCalls: ToolCall[]
+  currentContentBlocks: ContentBlock[]
   lastAssistantMessage: ChatMessage | undefined
 }
 
+/**
+ * Todos from the most recent todo content block (Codex todo_list), if any
+ */
+function todosFromBlocks(blocks: ContentBlock[]): Todo[] | null {
+  for (let i = blocks.length - 1; i >= 0; i--) {
+    const block = blocks[i]
+    if (block?.type === 'todo') {
+      return block.items.map(item => ({
+        content: item.text,
+        activeForm: item.text,
+        status: item.status,
+      }))
+    }
+  }
+  return null
+}
+
 /**
  * Extracts active todos and agents from streaming tool calls or last assistant message.
  * Includes dismissal state management for both.
@@ -17,6 +41,7 @@ export function useActiveTodosAndAgents({
   activeSessionId,
   isSending,
   currentToolCalls,
+  currentContentBlocks,
   lastAssistantMessage,
 }: UseActiveTodosAndAgentsParams) {
   // Track which message's todos were dismissed
@@ -46,6 +71,13 @@ export function useActiveTodosAndAgents({
       }
     }
 
+    if (isSending) {
+      const todos = todosFromBlocks(currentContentBlocks)
+      if (todos) {
+        return { todos, sourceMessageId: null, isFromStreaming: true }
+      }
+    }
+
     if (lastAssistantMessage?.tool_calls) {
       for (let i = lastAssistantMessage.tool_calls.length - 1; i >= 0; i--) {
         const tc = lastAssistantMessage.tool_calls[i]
@@ -59,8 +91,25 @@ export function useActiveTodosAndAgents({
       }
     }
 
+    if (lastAssistantMessage) {
+      const todos = todosFromBlocks(lastAssistantMessage.content_blocks ?? [])
+      if (todos) {
+        return {
+          todos,
+          sourceMessageId: lastAssistantMessage.id,
+          isFromStreaming: false,
+        }
+      }
+    }
+
     return { todos: [], sourceMessageId: null, isFromStreaming: false }
-  }, [activeSessionId, isSending, currentToolCalls, lastAssistantMessage])
+  }, [
+    activeSessionId,
+    isSending,
+    currentToolCalls,
+    currentContentBlocks,
+    lastAssistantMessage,
+  ])
 
   // Track which message's agents were dismissed
   const [dismissedAgentMessageId, setDismissedAgentMessageId] = useState<
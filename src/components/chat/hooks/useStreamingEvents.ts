@@ -28,8 +28,18 @@ import type {
   ToolUseEvent,
   ToolBlockEvent,
   ToolResultEvent,
+  ToolProgressEvent,
+  EmptyResponseEvent,
+  ToolDeniedEvent,
+  ToolErrorEvent,
+  TodoEvent,
   DoneEvent,
   ErrorEvent,
+  WarningEvent,
+  StallEvent,
+  ServerStderrEvent,
+  StderrEvent,
+  ContextWarningEvent,
   CancelledEvent,
   ThinkingEvent,
   PermissionDeniedEvent,
@@ -198,6 +208,8 @@ export default function useStreamingEvents({
       addTextBlock,
       addToolBlock,
       addThinkingBlock,
+      addErrorBlock,
+      setTodoBlock,
       addSendingSession,
     } = useChatStore.getState()
 
@@ -300,6 +312,16 @@ export default function useStreamingEvents({
       }
     )
 
+    const unlistenToolError = listen<ToolErrorEvent>('chat:tool_error', event => {
+      const { session_id, tool_use_id, message } = event.payload
+      addErrorBlock(session_id, tool_use_id, message)
+    })
+
+    const unlistenTodo = listen<TodoEvent>('chat:todo', event => {
+      const { session_id, id, items } = event.payload
+      setTodoBlock(session_id, id, items)
+    })
+
     // Buffer thinking deltas and flush on animation frames (same pattern as chunks).
     // OpenCode/Codex stream thinking as frequent small deltas; without batching,
     // each delta triggers a store mutation + re-render.
@@ -328,7 +350,8 @@ export default function useStreamingEvents({
     const unlistenToolResult = listen<ToolResultEvent>(
       'chat:tool_result',
       event => {
-        const { session_id, tool_use_id, output } = event.payload
+        const { session_id, tool_use_id, output, full_output_path } =
+          event.payload
 
         // Check if this tool was in pending denials - if so, it ran anyway
         // (e.g., yolo mode, or tool was pre-approved via allowedTools)
@@ -351,11 +374,60 @@ export default function useStreamingEvents({
         updateToolCallOutput(
           session_id,
           tool_use_id,
-          toolCall?.name === 'Read' ? '' : output
+          toolCall?.name === 'Read' ? '' : output,
+          full_output_path
         )
       }
     )
 
+    // Live output from a long-running command, appended until the final result
+    const unlistenToolProgress = listen<ToolProgressEvent>(
+      'chat:tool_progress',
+      event => {
+        const { session_id, tool_use_id, output } = event.payload
+        const toolCall = useChatStore
+          .getState()
+          .activeToolCalls[session_id]?.find(tc => tc.id === tool_use_id)
+        updateToolCallOutput(
+          session_id,
+          tool_use_id,
+          (toolCall?.output ?? '') + output
+        )
+      }
+    )
+
+    // Codex finished without text or tools; say so instead of a blank bubble
+    const unlistenEmptyResponse = listen<EmptyResponseEvent>(
+      'chat:empty_response',
+      event => {
+        const { session_id } = event.payload
+        const { lastSentMessages, setInputDraft } = useChatStore.getState()
+        const lastMessage = lastSentMessages[session_id]
+        toast.warning('The model returned an empty response', {
+          id: `empty-response-${session_id}`,
+          action: lastMessage
+            ? {
+                label: 'Reuse prompt',
+                onClick: () => setInputDraft(session_id, lastMessage),
+              }
+            : undefined,
+        })
+      }
+    )
+
+    const unlistenToolDenied = listen<ToolDeniedEvent>(
+      'chat:tool_denied',
+      event => {
+        const { session_id, worktree_id, command, message } = event.payload
+        const label = lookupSessionLabel(queryClient, session_id, worktree_id)
+        const prefix = label ? `${label}: ` : ''
+        toast.warning(`${prefix}Command blocked: ${command}`, {
+          id: `tool-denied-${session_id}`,
+          description: message,
+        })
+      }
+    )
+
     // Handle permission denied events (tools that require approval)
     const unlistenPermissionDenied = listen<PermissionDeniedEvent>(
       'chat:permission_denied',
@@ -1480,6 +1552,52 @@ export default function useStreamingEvents({
       }
     )
 
+    // Non-fatal backend warnings (e.g. resuming on a different OpenCode server)
+    const unlistenWarning = listen<WarningEvent>('chat:warning', event => {
+      toast.warning(event.payload.message)
+    })
+
+    // Codex went quiet while still running; tell the user rather than look frozen
+    const unlistenStalled = listen<StallEvent>('chat:stalled', event => {
+      const { session_id, worktree_id } = event.payload
+      const label = lookupSessionLabel(queryClient, session_id, worktree_id)
+      toast.info(
+        label ? `Codex is taking a while: ${label}` : 'Codex is taking a while…',
+        { id: `stalled-${session_id}` }
+      )
+    })
+
+    const unlistenResumed = listen<StallEvent>('chat:resumed', event => {
+      toast.dismiss(`stalled-${event.payload.session_id}`)
+    })
+
+    // Codex stderr diagnostics (kept out of the JSONL stream; debug only)
+    const unlistenStderr = listen<StderrEvent>('chat:stderr', event => {
+      console.debug('[useStreamingEvents] Codex stderr:', event.payload.line)
+    })
+    const unlistenServerStderr = listen<ServerStderrEvent>(
+      'codex-server:stderr',
+      event => {
+        console.debug(
+          '[useStreamingEvents] Codex server stderr:',
+          event.payload.line
+        )
+      }
+    )
+
+    // Codex conversation is close to its context window; suggest a fresh session
+    const unlistenContextWarning = listen<ContextWarningEvent>(
+      'chat:context_warning',
+      event => {
+        const { session_id, worktree_id, percent_used } = event.payload
+        const label = lookupSessionLabel(queryClient, session_id, worktree_id)
+        toast.warning(
+          `${label || 'This session'} has used ${percent_used}% of the model's context. Consider starting a new session.`,
+          { id: `context-warning-${session_id}` }
+        )
+      }
+    )
+
     // Handle context compaction events
     const unlistenCompacting = listen<CompactingEvent>(
       'chat:compacting',
@@ -1577,6 +1695,17 @@ export default function useStreamingEvents({
       unlistenPermissionDenied.then(f => f())
       unlistenDone.then(f => f())
       unlistenError.then(f => f())
+      unlistenWarning.then(f => f())
+      unlistenStalled.then(f => f())
+      unlistenResumed.then(f => f())
+      unlistenStderr.then(f => f())
+      unlistenServerStderr.then(f => f())
+      unlistenContextWarning.then(f => f())
+      unlistenToolProgress.then(f => f())
+      unlistenEmptyResponse.then(f => f())
+      unlistenToolDenied.then(f => f())
+      unlistenToolError.then(f => f())
+      unlistenTodo.then(f => f())
       unlistenCancelled.then(f => f())
       unlistenCompacting.then(f => f())
       unlistenCompacted.then(f => f())
@@ -31,6 +31,14 @@ function blocksMatch(
         return streamingBlock.type === 'tool_use'
           ? streamingBlock.tool_call_id === block.tool_call_id
           : false
+      case 'error':
+        return streamingBlock.type === 'error'
+          ? streamingBlock.tool_call_id === block.tool_call_id
+          : false
+      case 'todo':
+        return streamingBlock.type === 'todo'
+          ? streamingBlock.id === block.id
+          : false
     }
   })
 }
@@ -118,6 +118,7 @@ export type TimelineItem =
   | { type: 'askUserQuestion'; tool: ToolCall; introText?: string; key: string }
   | { type: 'enterPlanMode'; tool: ToolCall; key: string }
   | { type: 'exitPlanMode'; tool: ToolCall; key: string }
+  | { type: 'toolError'; toolCallId: string; message: string; key: string }
   | { type: 'unknown'; rawType: string; rawData: unknown; key: string }
 
 /**
@@ -368,6 +369,16 @@ export function buildTimeline(
           key: `tool-${toolCall.id}`,
         })
       }
+    } else if (block.type === 'error') {
+      result.push({
+        type: 'toolError',
+        toolCallId: block.tool_call_id,
+        message: block.message,
+        key: `error-${i}`,
+      })
+    } else if (block.type === 'todo') {
+      // Todo lists are shown in the todo widget above the textarea
+      continue
     } else {
       // Unknown content block type — render a visible indicator
       result.push({
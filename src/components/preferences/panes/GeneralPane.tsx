@@ -2062,6 +2062,41 @@ export const GeneralPane: React.FC = () => {
             </InlineField>
           )}
 
+          <InlineField
+            label="Reasoning summary"
+            description="How much reasoning Codex streams back"
+          >
+            <Select
+              value={preferences?.codex_reasoning_summary ?? 'default'}
+              onValueChange={value => {
+                if (preferences) {
+                  patchPreferences.mutate({
+                    codex_reasoning_summary:
+                      value === 'default'
+                        ? null
+                        : (value as 'auto' | 'concise' | 'detailed' | 'none'),
+                  })
+                }
+              }}
+            >
+              <SelectTrigger className="w-96">
+                <SelectValue />
+              </SelectTrigger>
+              <SelectContent>
+                <SelectItem value="default">Codex default</SelectItem>
+                <SelectItem value="auto">Auto</SelectItem>
+                <SelectItem value="concise">Concise</SelectItem>
+                <SelectItem value="detailed">Detailed</SelectItem>
+                <SelectItem value="none">None</SelectItem>
+              </SelectContent>
+            </Select>
+          </InlineField>
+
+          <CodexProviderField
+            preferences={preferences}
+            patchPreferences={patchPreferences}
+          />
+
           {/* OpenCode subsection */}
           <div className="pt-2">
             <div className="text-sm font-semibold text-foreground/80 mb-3">
@@ -2160,6 +2195,30 @@ export const GeneralPane: React.FC = () => {
             />
           </InlineField>
 
+          <InlineField
+            label="Codex web search"
+            description="Cached results are cheaper; live fetches fresh pages"
+          >
+            <Select
+              value={preferences?.codex_web_search_mode ?? 'cached'}
+              onValueChange={value => {
+                if (preferences) {
+                  patchPreferences.mutate({
+                    codex_web_search_mode: value as 'live' | 'cached',
+                  })
+                }
+              }}
+            >
+              <SelectTrigger className="w-96">
+                <SelectValue />
+              </SelectTrigger>
+              <SelectContent>
+                <SelectItem value="cached">Cached</SelectItem>
+                <SelectItem value="live">Live</SelectItem>
+              </SelectContent>
+            </Select>
+          </InlineField>
+
           {isNativeApp() && (
             <InlineField label="Editor" description="App to open worktrees in">
               <Select
@@ -2598,3 +2657,47 @@ const AiLanguageField: FC<{
     </InlineField>
   )
 }
+
+const CodexProviderField: FC<{
+  preferences: AppPreferences | undefined
+  patchPreferences: ReturnType<typeof usePatchPreferences>
+}> = ({ preferences, patchPreferences }) => {
+  const [localValue, setLocalValue] = useState(
+    preferences?.codex_model_provider ?? ''
+  )
+
+  const hasChanges = localValue !== (preferences?.codex_model_provider ?? '')
+
+  const handleSave = useCallback(() => {
+    if (!preferences) return
+    patchPreferences.mutate({
+      codex_model_provider: localValue.trim() || null,
+    })
+  }, [preferences, patchPreferences, localValue])
+
+  return (
+    <InlineField
+      label="Model provider"
+      description="Provider ID from your Codex config (e.g. azure)"
+    >
+      <div className="flex items-center gap-2">
+        <Input
+          className="w-40"
+          placeholder="Default"
+          value={localValue}
+          onChange={e => setLocalValue(e.target.value)}
+        />
+        <Button
+          size="sm"
+          onClick={handleSave}
+          disabled={!hasChanges || patchPreferences.isPending}
+        >
+          {patchPreferences.isPending && (
+            <Loader2 className="h-4 w-4 animate-spin" />
+          )}
+          Save
+        </Button>
+      </div>
+    </InlineField>
+  )
+}
@@ -13,6 +13,7 @@ import {
   type PendingTextFile,
   type ContentBlock,
   type Todo,
+  type TodoListItem,
   type QueuedMessage,
   type PermissionDenial,
   type ExecutionMode,
@@ -313,7 +314,8 @@ interface ChatUIState {
   updateToolCallOutput: (
     sessionId: string,
     toolUseId: string,
-    output: string
+    output: string,
+    fullOutputPath?: string
   ) => void
   clearToolCalls: (sessionId: string) => void
 
@@ -321,6 +323,12 @@ interface ChatUIState {
   addTextBlock: (sessionId: string, text: string) => void
   addToolBlock: (sessionId: string, toolCallId: string) => void
   addThinkingBlock: (sessionId: string, thinking: string) => void
+  addErrorBlock: (
+    sessionId: string,
+    toolCallId: string,
+    message: string
+  ) => void
+  setTodoBlock: (sessionId: string, id: string, items: TodoListItem[]) => void
   clearStreamingContentBlocks: (sessionId: string) => void
   getStreamingContentBlocks: (sessionId: string) => ContentBlock[]
 
@@ -1058,14 +1066,21 @@ export const useChatStore = create<ChatUIState>()(
           'addToolCall'
         ),
 
-      updateToolCallOutput: (sessionId, toolUseId, output) =>
+      updateToolCallOutput: (sessionId, toolUseId, output, fullOutputPath) =>
         set(
           state => {
             const toolCalls = state.activeToolCalls[sessionId] ?? []
             const existing = toolCalls.find(tc => tc.id === toolUseId)
-            if (!existing || existing.output === output) return state
+            if (
+              !existing ||
+              (existing.output === output &&
+                existing.full_output_path === fullOutputPath)
+            )
+              return state
             const updatedToolCalls = toolCalls.map(tc =>
-              tc.id === toolUseId ? { ...tc, output } : tc
+              tc.id === toolUseId
+                ? { ...tc, output, full_output_path: fullOutputPath }
+                : tc
             )
             return {
               activeToolCalls: {
@@ -1168,6 +1183,45 @@ export const useChatStore = create<ChatUIState>()(
           'addThinkingBlock'
         ),
 
+      addErrorBlock: (sessionId, toolCallId, message) =>
+        set(
+          state => ({
+            streamingContentBlocks: {
+              ...state.streamingContentBlocks,
+              [sessionId]: [
+                ...(state.streamingContentBlocks[sessionId] ?? []),
+                { type: 'error', tool_call_id: toolCallId, message },
+              ],
+            },
+          }),
+          undefined,
+          'addErrorBlock'
+        ),
+
+      setTodoBlock: (sessionId, id, items) =>
+        set(
+          state => {
+            const blocks = state.streamingContentBlocks[sessionId] ?? []
+            const index = blocks.findIndex(
+              block => block.type === 'todo' && block.id === id
+            )
+            const todoBlock: ContentBlock = { type: 'todo', id, items }
+            // Updates replace the list in place so it keeps its original position
+            const newBlocks =
+              index === -1
+                ? [...blocks, todoBlock]
+                : blocks.map((block, i) => (i === index ? todoBlock : block))
+            return {
+              streamingContentBlocks: {
+                ...state.streamingContentBlocks,
+                [sessionId]: newBlocks,
+              },
+            }
+          },
+          undefined,
+          'setTodoBlock'
+        ),
+
       clearStreamingContentBlocks: sessionId =>
         set(
           state => {
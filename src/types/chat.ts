@@ -56,10 +56,20 @@ export interface ToolCall {
   output?: string
   /** Parent tool use ID for sub-agent tool calls (for parallel task attribution) */
   parent_tool_use_id?: string
+  /** Untruncated output file when `output` was capped (read via read_tool_output) */
+  full_output_path?: string
 }
 
 /**
- * A content block in a message - text, tool use, or thinking
+ * One entry of an agent-maintained todo list (Codex todo_list items)
+ */
+export interface TodoListItem {
+  text: string
+  status: 'pending' | 'completed'
+}
+
+/**
+ * A content block in a message - text, tool use, thinking, a tool failure, or a todo list
  * Used to preserve the order of content in Claude's response
  * Note: Uses snake_case to match Rust serde serialization (rename_all = "snake_case")
  */
@@ -67,6 +77,8 @@ export type ContentBlock =
   | { type: 'text'; text: string }
   | { type: 'tool_use'; tool_call_id: string }
   | { type: 'thinking'; thinking: string }
+  | { type: 'error'; tool_call_id: string; message: string }
+  | { type: 'todo'; id: string; items: TodoListItem[] }
 
 /**
  * A single chat message
@@ -98,6 +110,8 @@ export interface ChatMessage {
   recovered?: boolean
   /** Token usage for this message (assistant messages only) */
   usage?: UsageData
+  /** Files the turn edited, de-duplicated (Codex assistant messages only) */
+  changed_files?: string[]
 }
 
 // ============================================================================
@@ -140,8 +154,12 @@ export interface Session {
   claude_session_id?: string
   /** Codex CLI thread ID for resuming conversations */
   codex_thread_id?: string
+  /** Working directory the Codex thread was started in */
+  codex_thread_cwd?: string
   /** OpenCode session ID for resuming conversations */
   opencode_session_id?: string
+  /** OpenCode server URL the OpenCode session was created on */
+  server_url?: string
   /** Selected model for this session */
   selected_model?: string
   /** Selected thinking level for this session */
@@ -312,6 +330,12 @@ export interface DoneEvent {
   worktree_id: string // Kept for backward compatibility
   /** True when a Codex/Opencode plan-mode run completed with content */
   waiting_for_plan?: boolean
+  /** Why the turn ended, e.g. "stop" or "max_output_tokens" (Codex only) */
+  finish_reason?: string
+  /** Turn duration in milliseconds (Codex only) */
+  duration_ms?: number
+  /** Files the turn edited (Codex only) */
+  changed_files?: string[]
 }
 
 /**
@@ -340,6 +364,61 @@ export interface ErrorEvent {
   error: string
 }
 
+/**
+ * Event payload when Codex starts a turn, before the first chunk or tool
+ * (lets the UI show "generating" instead of idle on slow first tokens)
+ */
+export interface TurnStartEvent {
+  session_id: string
+  worktree_id: string
+  turn_id?: string
+}
+
+/**
+ * Event payload for chat:stderr (Codex diagnostics outside the JSONL stream)
+ */
+export interface StderrEvent {
+  session_id: string
+  worktree_id: string
+  line: string
+}
+
+/**
+ * Event payload for codex-server:stderr (Codex server diagnostics not tied to a session)
+ */
+export interface ServerStderrEvent {
+  line: string
+}
+
+/**
+ * Event payload for chat:context_warning (Codex conversation nearing its context window)
+ */
+export interface ContextWarningEvent {
+  session_id: string
+  worktree_id: string
+  percent_used: number
+  context_window: number
+}
+
+/**
+ * Event payload for chat:stalled / chat:resumed (Codex silent for a while)
+ */
+export interface StallEvent {
+  session_id: string
+  worktree_id: string
+  /** Seconds since the last event from Codex */
+  idle_secs: number
+}
+
+/**
+ * Event payload for non-fatal warnings from Rust (the run continues)
+ */
+export interface WarningEvent {
+  session_id: string
+  worktree_id: string
+  message: string
+}
+
 /**
  * Event payload for cancellation from Rust (user pressed Escape)
  */
@@ -378,6 +457,64 @@ export interface ToolResultEvent {
   worktree_id: string // Kept for backward compatibility
   tool_use_id: string
   output: string
+  /** Set when output was truncated; the full text is in this file */
+  full_output_path?: string
+  /** Codex file_change only: files that were applied */
+  applied?: string[]
+  /** Codex file_change only: files that could not be applied */
+  rejected?: string[]
+}
+
+/**
+ * Event payload for chat:empty_response (Codex turn completed with no text or tools)
+ */
+export interface EmptyResponseEvent {
+  session_id: string
+  worktree_id: string
+  finish_reason?: string
+}
+
+/**
+ * Event payload for chat:tool_error (a tool completed with a failure status)
+ */
+export interface ToolErrorEvent {
+  session_id: string
+  worktree_id: string
+  tool_use_id: string
+  message: string
+}
+
+/**
+ * Event payload for chat:todo (Codex todo list; replaces the list with the same id)
+ */
+export interface TodoEvent {
+  session_id: string
+  worktree_id: string
+  id: string
+  items: TodoListItem[]
+}
+
+/**
+ * Event payload for chat:tool_denied (Codex command blocked by the execution mode's sandbox)
+ */
+export interface ToolDeniedEvent {
+  session_id: string
+  worktree_id: string
+  tool_use_id: string
+  command: string
+  message: string
+}
+
+/**
+ * Event payload for chat:tool_progress (live output from a running Codex command)
+ * The final chat:tool_result replaces whatever was accumulated.
+ */
+export interface ToolProgressEvent {
+  session_id: string
+  worktree_id: string
+  tool_use_id: string
+  /** Output appended since the previous progress event */
+  output: string
 }
 
 // ============================================================================
@@ -932,6 +1069,10 @@ export interface RunLogFileInfo {
   user_message_preview: string
   /** Token usage for this run (if completed) */
   usage?: UsageData
+  /** CLI binary that served this run */
+  cli_path?: string
+  /** Version reported by that binary */
+  cli_version?: string
 }
 
 /**
@@ -961,6 +961,18 @@ export interface AppPreferences {
   codex_cli_source: 'jean' | 'path' // Codex CLI source: 'jean' (managed) or 'path' (system PATH)
   opencode_cli_source: 'jean' | 'path' // OpenCode CLI source: 'jean' (managed) or 'path' (system PATH)
   gh_cli_source: 'jean' | 'path' // GitHub CLI source: 'jean' (managed) or 'path' (system PATH)
+  opencode_external_server_url: string | null // External OpenCode server to use instead of spawning one, null = Jean-managed
+  codex_tool_output_max_kb: number // Truncate Codex command outputs above this size in KB (full output saved to disk)
+  codex_web_search_mode: 'live' | 'cached' // Codex web search when web tools are allowed: 'live' or 'cached'
+  codex_reasoning_summary: 'auto' | 'concise' | 'detailed' | 'none' | null // Codex reasoning summary verbosity, null = Codex default
+  opencode_server_spawn_retries: number // Extra OpenCode server spawn attempts when the health check times out
+  codex_stall_warning_secs: number // Warn when Codex sends nothing for this many seconds (0 = never)
+  codex_model_provider: string | null // Codex model_provider override, e.g. an Azure or gateway provider from Codex config, null = OpenAI
+  opencode_server_memory_limit_mb: number | null // Unix data-segment (heap) cap for the managed OpenCode server in MB, null = unlimited
+  opencode_serve_subcommand: string | null // Subcommand that starts the OpenCode server, null = "serve"
+  opencode_extra_serve_args: string[] // Extra args appended after --hostname/--port when starting the OpenCode server
+  max_concurrent_tailers: number // Most sessions that may stream output at once; new ones are rejected beyond this (0 = unlimited)
+  opencode_server_per_project: boolean // Run a separate managed OpenCode server per project instead of one shared server
 }
 
 export interface CustomCliProfile {
@@ -1526,4 +1538,16 @@ export const defaultPreferences: AppPreferences = {
   codex_cli_source: 'jean', // Default: Jean-managed
   opencode_cli_source: 'jean', // Default: Jean-managed
   gh_cli_source: 'jean', // Default: Jean-managed
+  opencode_external_server_url: null, // Default: Jean-managed server
+  codex_tool_output_max_kb: 256, // Default: 256 KB
+  codex_web_search_mode: 'cached', // Default: cached (Codex's own default)
+  codex_reasoning_summary: null, // Default: Codex decides
+  opencode_server_spawn_retries: 2, // Default: 2 retries
+  codex_stall_warning_secs: 30, // Default: 30 seconds
+  codex_model_provider: null, // Default: Codex default provider
+  opencode_server_memory_limit_mb: null, // Default: no limit
+  opencode_serve_subcommand: null, // Default: serve
+  opencode_extra_serve_args: [], // Default: none
+  max_concurrent_tailers: 32, // Default: 32 sessions
+  opencode_server_per_project: false, // Default: one shared server
 }